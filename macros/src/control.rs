@@ -0,0 +1,86 @@
+//! Checks of `break` and `continue` in clauses executed inside loop of `select!`.
+//!
+//! Clauses of `repeat` branches and `else` clauses are executed inside a loop generated by `select!`,
+//! so unlabeled `break` and `continue` there would target the generated loop but not enclosing loops
+//! of users. They are rejected. Labeled ones are fine as labels of users never name the generated
+//! loop.
+
+use quote::ToTokens;
+use syn::visit::{self, Visit};
+use syn::{ExprAsync, ExprBreak, ExprClosure, ExprContinue, ExprForLoop, ExprLoop, ExprWhile, Item, Result};
+
+use crate::Select;
+
+struct LoopControls<'a> {
+    what: &'a str,
+    error: Option<syn::Error>,
+}
+
+impl LoopControls<'_> {
+    fn reject(&mut self, tokens: &dyn ToTokens, keyword: &str) {
+        let error = syn::Error::new_spanned(
+            tokens,
+            format!(
+                "`select!`: unlabeled `{keyword}` in {} targets loop inside `select!`, label the enclosing loop to \
+                 target it",
+                self.what
+            ),
+        );
+        match self.error.as_mut() {
+            None => self.error = Some(error),
+            Some(first) => first.combine(error),
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for LoopControls<'_> {
+    fn visit_expr_break(&mut self, expr: &'ast ExprBreak) {
+        if expr.label.is_none() {
+            self.reject(&expr.break_token, "break");
+        }
+        visit::visit_expr_break(self, expr);
+    }
+
+    fn visit_expr_continue(&mut self, expr: &'ast ExprContinue) {
+        if expr.label.is_none() {
+            self.reject(expr, "continue");
+        }
+    }
+
+    // Unlabeled `break` and `continue` in bodies of nested loops target these loops.
+    fn visit_expr_for_loop(&mut self, expr: &'ast ExprForLoop) {
+        self.visit_expr(&expr.expr);
+    }
+
+    fn visit_expr_loop(&mut self, _expr: &'ast ExprLoop) {}
+
+    fn visit_expr_while(&mut self, expr: &'ast ExprWhile) {
+        self.visit_expr(&expr.cond);
+    }
+
+    // `break` and `continue` never cross closures, async blocks and items.
+    fn visit_expr_closure(&mut self, _expr: &'ast ExprClosure) {}
+
+    fn visit_expr_async(&mut self, _expr: &'ast ExprAsync) {}
+
+    fn visit_item(&mut self, _item: &'ast Item) {}
+}
+
+/// Rejects unlabeled `break` and `continue` in clauses executed inside loop of `select!`.
+pub(crate) fn check(select: &Select) -> Result<()> {
+    let mut controls = LoopControls { what: "", error: None };
+    for branch in select.branches.iter() {
+        if branch.repeat.is_some() {
+            controls.what = "clause of `repeat` branch";
+            controls.visit_expr(&branch.clause.expr);
+        }
+        if let Some((_, clause)) = branch.else_clause.as_ref().and_then(|else_clause| else_clause.arm.as_ref()) {
+            controls.what = "`else` clause";
+            controls.visit_expr(&clause.expr);
+        }
+    }
+    match controls.error {
+        None => Ok(()),
+        Some(error) => Err(error),
+    }
+}
@@ -0,0 +1,641 @@
+//! Code generation of `select!` flavours polling branches inline, that is all but `compact`.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote_spanned};
+use syn::{Expr, Ident, Index, Path};
+
+use crate::{define_output_enum, Branch, Fairness, Flavor, Select};
+
+/// Generates pieces of a `select!` flavour and assembles them.
+pub(crate) struct Expander<'a> {
+    krate: &'a Path,
+    // Context of `poll_select!`, other flavours poll in `poll_fn`.
+    cx: Option<&'a Expr>,
+    select: &'a Select,
+    flavor: Flavor,
+    branch_names: Vec<Ident>,
+    unmatched_names: Vec<Ident>,
+    output_enum: TokenStream,
+}
+
+impl<'a> Expander<'a> {
+    pub(crate) fn new(krate: &'a Path, cx: Option<&'a Expr>, select: &'a Select, flavor: Flavor) -> Self {
+        let span = Span::mixed_site();
+        let output_ident = Ident::new("__SelectOutput", span);
+        let (branch_names, unmatched_names, output_enum) = define_output_enum(&output_ident, &select.branches, span);
+        Expander { krate, cx, select, flavor, branch_names, unmatched_names, output_enum }
+    }
+
+    fn n_branches(&self) -> usize {
+        self.select.branches.len()
+    }
+
+    fn fairness(&self) -> Fairness {
+        self.select.fair.map(|(_, fairness)| fairness).unwrap_or_default()
+    }
+
+    /// Whether there are `repeat` futures to rearm. Streams are kept until end, there is no need to
+    /// rearm.
+    fn rearming(&self) -> bool {
+        self.select.branches.iter().any(|branch| branch.repeat.is_some() && branch.stream.is_none())
+    }
+
+    /// Whether to tell whether any branch is pending, for `complete` or panic on exhaustion.
+    fn tracking_pending(&self) -> bool {
+        self.select.complete_clause.is_some() || self.select.default_clause.is_none()
+    }
+
+    /// Seed is evaluated before futures.
+    fn seeding(&self) -> TokenStream {
+        let krate = self.krate;
+        match (self.select.seed.as_ref(), self.fairness()) {
+            (Some((_, seed)), _) => quote_mixed! {
+                let mut __select_rng = #krate::__private::Rng::new(#seed);
+            },
+            (None, Fairness::RoundRobin) => quote_mixed! {
+                let __select_round_robin = {
+                    static ROUND_ROBIN: #krate::__private::RoundRobin = #krate::__private::RoundRobin::new();
+                    &ROUND_ROBIN
+                };
+            },
+            (None, Fairness::Random) => quote_mixed! {},
+        }
+    }
+
+    /// Futures are evaluated in order before looping, so they are kept across iterations.
+    fn storage(&self) -> TokenStream {
+        let seeding = self.seeding();
+        let branch_futures = self.select.branches.iter().map(|branch| branch.conditional_future());
+        if !self.select.branches.iter().any(Branch::rearmable) {
+            return quote_mixed! {
+                #seeding
+                let mut __select_futures = (#(#branch_futures,)*);
+            };
+        }
+        let span = Span::mixed_site();
+        let bindings = self.select.branches.iter().enumerate().map(|(i, branch)| {
+            let ident = format_ident!("__select_future_{i}", span = span);
+            match branch.rearmable() {
+                false => quote_mixed! { #ident },
+                true => quote_mixed! { mut #ident },
+            }
+        });
+        let elements = self.select.branches.iter().enumerate().map(|(i, branch)| {
+            let ident = format_ident!("__select_future_{i}", span = span);
+            match branch.rearmable() {
+                false => quote_mixed! { #ident },
+                true => quote_mixed! { () },
+            }
+        });
+        quote_mixed! {
+            #seeding
+            let (#(#bindings,)*) = (#(#branch_futures,)*);
+            let mut __select_futures = (#(#elements,)*);
+        }
+    }
+
+    fn borrowing(&self) -> TokenStream {
+        let krate = self.krate;
+        let n_branches = self.n_branches();
+        // Branches are polled directly in `poll_select!` as there is no storage for wakes.
+        let wakes = match self.flavor {
+            Flavor::Poll => quote_mixed! {},
+            _ => quote_mixed! {
+                let __select_wakes =
+                    #krate::__private::WakeSet::<#n_branches, { #krate::__private::wake_words(#n_branches) }>::new();
+            },
+        };
+        quote_mixed! {
+            // Shadow it so it won't be moved accidentally.
+            let mut __select_futures = &mut __select_futures;
+            #wakes
+        }
+    }
+
+    /// Only selected `repeat` branch is rearmed, branches disabled by mismatches stay disabled.
+    fn rearming_declaration(&self) -> TokenStream {
+        match self.rearming() {
+            false => quote_mixed! {},
+            true => quote_mixed! {
+                let mut __select_rearming = ::core::option::Option::<usize>::None;
+            },
+        }
+    }
+
+    fn declaration(&self) -> TokenStream {
+        let storage = self.storage();
+        let borrowing = self.borrowing();
+        let rearming = self.rearming_declaration();
+        quote_mixed! {
+            #storage
+            #borrowing
+            #rearming
+        }
+    }
+
+    /// Rearms future of branch `index`, previous future is dropped first to end its borrows.
+    fn rearm(&self, index: usize, branch: &Branch) -> TokenStream {
+        let slot = branch.future_slot(index);
+        let future = branch.conditional_future();
+        let index = Index::from(index);
+        let reporting = self.select.reporting(self.krate, &index, "Disabled");
+        quote_mixed! {
+            #[allow(clippy::drop_non_drop)]
+            ::core::mem::drop(#slot);
+            #slot = #future;
+            __select_wakes.reset(#index);
+            #reporting
+        }
+    }
+
+    /// Rearms selected `repeat` branch at the start of next iteration.
+    fn arming(&self) -> TokenStream {
+        if !self.rearming() {
+            return quote_mixed! {};
+        }
+        let armings = self
+            .select
+            .branches
+            .iter()
+            .enumerate()
+            .filter(|(_, branch)| branch.repeat.is_some() && branch.stream.is_none())
+            .map(|(i, branch)| {
+                let index = Index::from(i);
+                let rearm = self.rearm(i, branch);
+                quote_mixed! {
+                    if __select_rearming == ::core::option::Option::Some(#index) {
+                        #rearm
+                    }
+                }
+            });
+        quote_mixed! {
+            #(#armings)*
+            __select_rearming = ::core::option::Option::None;
+        }
+    }
+
+    /// Polling order of branches as statements before selection, statements before each round of
+    /// polling and binding of `branch` in `i`th poll.
+    fn ordering(&self) -> (TokenStream, TokenStream, TokenStream) {
+        let krate = self.krate;
+        let n_branches = self.n_branches();
+        if self.select.biased.is_some() {
+            return (quote_mixed! {}, quote_mixed! {}, quote_mixed! { let branch = i; });
+        }
+        let random = match self.select.seed {
+            None => quote_mixed! { #krate::__private::random_u32 },
+            Some(_) => quote_mixed! { || __select_rng.next_u32() },
+        };
+        // Weighted branches are polled in weighted random order. It is drawn once per selection, as
+        // drawing costs quadratic time in number of branches.
+        if self.select.branches.iter().any(|branch| branch.weight.is_some()) {
+            let weights =
+                self.select.branches.iter().map(|branch| branch.weight.as_ref().map_or(1, |weight| weight.value));
+            return (
+                quote_mixed! {
+                    let weights: [u32; #n_branches] = [#(#weights),*];
+                    let mut order = [0usize; #n_branches];
+                    #krate::__private::weighted_order(&weights, &mut order, #random);
+                },
+                quote_mixed! {},
+                quote_mixed! { let branch = order[i]; },
+            );
+        }
+        let start = match self.fairness() {
+            _ if self.select.seed.is_some() => quote_mixed! {
+                let start = __select_rng.next(#n_branches);
+            },
+            Fairness::Random => quote_mixed! {
+                let start = #krate::__private::random(#n_branches);
+            },
+            Fairness::RoundRobin => quote_mixed! {
+                let start = __select_round_robin.start(#n_branches);
+            },
+        };
+        (quote_mixed! {}, start, quote_mixed! {
+            #[allow(clippy::modulo_one)]
+            let branch = (start + i) % #n_branches;
+        })
+    }
+
+    /// Match arm to poll branch `i`.
+    fn branch_polling(&self, i: usize, branch: &Branch) -> TokenStream {
+        let krate = self.krate;
+        let select = self.select;
+        let matching = branch.matching(None);
+        let error_passing = select.error_passing(i, quote_mixed! {});
+        let span = branch.span();
+        let index = Index::from(i);
+        let slot = branch.future_slot(i);
+        let name = &self.branch_names[i];
+        let (mismatched, ended, selected) = (
+            select.reporting(krate, &index, "Mismatched"),
+            select.reporting(krate, &index, "Ended"),
+            select.reporting(krate, &index, "Selected"),
+        );
+        let enabling = select.nopanic.map(|_| quote_mixed! { __select_enabled = true; });
+        // Kept futures are moved after polling, so they must be `Unpin`.
+        let pinning = match select.keep {
+            None => quote_mixed! {
+                #[allow(unused_unsafe)]
+                let future = unsafe {
+                    ::core::pin::Pin::new_unchecked(future)
+                };
+            },
+            Some(_) => quote_mixed! {
+                let future = ::core::pin::Pin::new(future);
+            },
+        };
+        let pending = self.tracking_pending().then(|| quote_mixed! { any_pending = true; });
+        let mismatching = match branch.else_clause.as_ref() {
+            None => quote_spanned! {span=>
+                #slot = ::core::option::Option::None;
+                #mismatched
+                continue;
+            },
+            Some(else_clause) => {
+                let unmatched = &self.unmatched_names[i];
+                // Stream is kept to yield more items if it is re-enabled.
+                let disabling = match else_clause.continuing.is_some() && branch.stream.is_some() {
+                    true => quote_spanned! {span=>},
+                    false => quote_spanned! {span=> #slot = ::core::option::Option::None; },
+                };
+                quote_spanned! {span=>
+                    #disabling
+                    #mismatched
+                    return ::core::task::Poll::Ready(__SelectOutput::#unmatched(output));
+                }
+            },
+        };
+        let (poll, ready) = match branch.stream {
+            None => (quote_spanned! {span=> ::core::future::Future::poll(future, cx) }, quote_spanned! {span=>
+                ::core::task::Poll::Ready(output) => {
+                    #slot = ::core::option::Option::None;
+                    output
+                },
+            }),
+            Some(_) => {
+                let waking = match self.flavor {
+                    Flavor::Poll => quote_spanned! {span=>},
+                    _ => quote_spanned! {span=> __select_wakes.wake(#index); },
+                };
+                (quote_spanned! {span=> #krate::Stream::poll_next(future, cx) }, quote_spanned! {span=>
+                    ::core::task::Poll::Ready(::core::option::Option::Some(output)) => {
+                        // Stream could have more items ready.
+                        #waking
+                        output
+                    },
+                    ::core::task::Poll::Ready(::core::option::Option::None) => {
+                        #slot = ::core::option::Option::None;
+                        #ended
+                        continue;
+                    },
+                })
+            },
+        };
+        let poll = match self.flavor {
+            Flavor::Poll => poll,
+            _ => quote_spanned! {span=> __select_wakes.poll(#index, cx, |cx| #poll) },
+        };
+        let rearming = match branch.repeat.is_some() && branch.stream.is_none() {
+            false => quote_spanned! {span=>},
+            true => quote_spanned! {span=> __select_rearming = ::core::option::Option::Some(#index); },
+        };
+        // Completed futures stay in place in `poll_select!`, so mismatches without `else` are rejected.
+        let irrefutable = match self.flavor == Flavor::Poll && branch.stream.is_none() && branch.else_clause.is_none() {
+            false => quote_spanned! {span=>},
+            true => {
+                let check = &branch.check;
+                quote_spanned! {span=>
+                    #[allow(unused_variables)]
+                    #[allow(unreachable_code)]
+                    #[allow(unused_parens)]
+                    if false {
+                        let (#check) = output;
+                        ::core::unreachable!();
+                    }
+                }
+            },
+        };
+        let rotating = match self.fairness() {
+            Fairness::RoundRobin => quote_spanned! {span=> __select_round_robin.select(#index); },
+            Fairness::Random => quote_spanned! {span=>},
+        };
+        quote_spanned! {span=>
+            #index => {
+                let ::core::option::Option::Some(future) = #slot.as_mut() else {
+                    continue;
+                };
+                #enabling
+                #pinning
+                let mut output = match #poll {
+                    #ready
+                    ::core::task::Poll::Pending => {
+                        #pending
+                        continue;
+                    },
+                };
+                #irrefutable
+                let mut __select_matched = false;
+                #[allow(unreachable_patterns)]
+                #[allow(unused_variables)]
+                #[allow(unused_mut)]
+                match output {
+                    #matching
+                    #error_passing
+                    _ => {},
+                };
+                if !__select_matched {
+                    #mismatching
+                }
+                #krate::__private::record(#index);
+                #selected
+                #rearming
+                #rotating
+                return ::core::task::Poll::Ready(__SelectOutput::#name(output));
+            }
+        }
+    }
+
+    /// Polls branches once, evaluates to `__SelectOutput` or `Poll` of it in `poll_select!`.
+    fn polling(&self) -> TokenStream {
+        let n_branches = self.n_branches();
+        let (ordering, start, branch) = self.ordering();
+        let pollings = self.select.branches.iter().enumerate().map(|(i, branch)| self.branch_polling(i, branch));
+        let (pending_declaration, pending_check) = match self.tracking_pending() {
+            true => (quote_mixed! { let mut any_pending = false; }, quote_mixed! {
+                if !any_pending {
+                    return ::core::task::Poll::Ready(__SelectOutput::Completed);
+                }
+            }),
+            false => (quote_mixed! {}, quote_mixed! {}),
+        };
+        let blocking = match self.select.default_clause.is_some() {
+            true => quote_mixed! { ::core::task::Poll::Ready(__SelectOutput::WouldBlock) },
+            false => quote_mixed! { ::core::task::Poll::Pending },
+        };
+        let poll = quote_mixed! {
+            |cx: &mut ::core::task::Context<'_>| {
+                #start
+                #pending_declaration
+                for i in 0..#n_branches {
+                    #branch
+                    match branch {
+                        #(#pollings)*
+                        _ => ::core::unreachable!("select! encounter mismatch branch in polling"),
+                    }
+                }
+                #pending_check
+                #blocking
+            }
+        };
+        match self.cx {
+            None => quote_mixed! {{
+                #ordering
+                ::core::future::poll_fn(#poll).await
+            }},
+            Some(cx) => quote_mixed! {{
+                #ordering
+                (#poll)(#cx)
+            }},
+        }
+    }
+
+    /// Match arms handling mismatched outputs of branches with `else` clauses.
+    fn else_arms(&self) -> Vec<TokenStream> {
+        self.select
+            .branches
+            .iter()
+            .enumerate()
+            .filter_map(|(i, branch)| branch.else_clause.as_ref().map(|else_clause| (i, branch, else_clause)))
+            .map(|(i, branch, else_clause)| {
+                let unmatched = &self.unmatched_names[i];
+                let (pat, clause) = match else_clause.arm.as_ref() {
+                    None => (quote_mixed! { _ }, quote_mixed! {}),
+                    Some((pat, clause)) => (quote_mixed! { #pat }, quote_mixed! { #clause; }),
+                };
+                // Errors are not mismatches, they are passed to `error` clause before mismatching.
+                let unwrapping = (i < self.select.fallible).then(|| {
+                    quote_mixed! {
+                        let ::core::result::Result::Ok(output) = output else {
+                            ::core::unreachable!("select! encounter error in mismatched output");
+                        };
+                    }
+                });
+                let rearming = match else_clause.continuing.is_some() && branch.stream.is_none() {
+                    false => quote_mixed! {},
+                    true => self.rearm(i, branch),
+                };
+                // Clauses could diverge by labeled `break` or `continue`. Mismatched outputs have
+                // nowhere else to go, so `else` patterns must be irrefutable.
+                quote_mixed! {
+                    #[allow(unreachable_code)]
+                    __SelectOutput::#unmatched(output) => {
+                        #unwrapping
+                        #[allow(unused_parens)]
+                        let (#pat) = output;
+                        #clause
+                        #rearming
+                    },
+                }
+            })
+            .collect()
+    }
+
+    /// Matches `output` to execute its clause.
+    fn matching(&self, else_arms: &[TokenStream]) -> TokenStream {
+        let krate = self.krate;
+        let select = self.select;
+        let looping = self.flavor == Flavor::Loop;
+        let default_attrs = select.default_clause.as_ref().map(|(_, clause)| clause.arm_attrs());
+        let default_handler = match select.default_clause.as_ref() {
+            None => quote_mixed! { ::core::unreachable!("not in unblocking mode") },
+            Some((_, clause)) => quote_mixed! { #clause },
+        };
+        let complete_attrs = select.complete_clause.as_ref().map(|(_, clause)| clause.arm_attrs());
+        let complete_handler = match select.complete_clause.as_ref() {
+            None => select.exhausting(krate),
+            Some((_, clause)) => select.complete_handler(clause),
+        };
+        // Clauses of `repeat` branches are handled inside `select!`.
+        let matching_branches: Vec<_> = select
+            .branches
+            .iter()
+            .zip(self.branch_names.iter())
+            .filter(|(branch, _)| looping || branch.repeat.is_none())
+            .collect();
+        let matching_names = matching_branches.iter().map(|(_, name)| name);
+        let branch_attrs = matching_branches.iter().map(|(branch, _)| branch.clause.arm_attrs());
+        let branch_handlers = matching_branches.iter().map(|(branch, _)| &branch.clause);
+        let branch_bindings = matching_branches.iter().map(|(branch, _)| &branch.bind);
+        // Mismatched outputs are handled inside `select!`.
+        let else_arms = else_arms.iter().filter(|_| looping);
+        let branch_variants: Vec<_> =
+            self.branch_names.iter().map(|name| quote_mixed! { __SelectOutput::#name }).collect();
+        let error_arm = select.error_arm(&branch_variants);
+        let matching = quote_mixed! {
+            match output {
+                #default_attrs
+                __SelectOutput::WouldBlock => #default_handler,
+                #complete_attrs
+                __SelectOutput::Completed => #complete_handler,
+                #(
+                    #branch_attrs
+                    __SelectOutput::#matching_names(#branch_bindings) => #branch_handlers,
+                )*
+                #(#else_arms)*
+                #error_arm
+                #[allow(unreachable_patterns)] // In case of refutable patterns in branches
+                _ => ::core::unreachable!("select! fail to pattern match"),
+            }
+        };
+        match self.flavor {
+            Flavor::Indexed => {
+                let default = quote_mixed! { __SelectOutput::WouldBlock };
+                let complete = quote_mixed! { __SelectOutput::Completed };
+                let indexing = select.indexing(krate, &branch_variants, default, complete);
+                quote_mixed! { (#indexing, #matching) }
+            },
+            _ => matching,
+        }
+    }
+
+    /// Drives `repeat` and `else` branches inside `select!` until others are selected.
+    fn driving(&self, else_arms: &[TokenStream]) -> TokenStream {
+        let polling = self.polling();
+        let repeating = self.select.branches.iter().any(|branch| branch.repeat.is_some());
+        if !repeating && else_arms.is_empty() {
+            return polling;
+        }
+        let arming = self.arming();
+        let repeat_branches: Vec<_> = self
+            .select
+            .branches
+            .iter()
+            .zip(self.branch_names.iter())
+            .filter(|(branch, _)| branch.repeat.is_some())
+            .collect();
+        let repeat_names = repeat_branches.iter().map(|(_, name)| name);
+        let repeat_bindings = repeat_branches.iter().map(|(branch, _)| &branch.bind);
+        let repeat_attrs = repeat_branches.iter().map(|(branch, _)| branch.clause.arm_attrs());
+        let repeat_handlers = repeat_branches.iter().map(|(branch, _)| &branch.clause);
+        quote_mixed! {
+            loop {
+                #arming
+                let mut output = #polling;
+                match output {
+                    #(
+                        #repeat_attrs
+                        __SelectOutput::#repeat_names(#repeat_bindings) => #repeat_handlers,
+                    )*
+                    #(#else_arms)*
+                    output => break output,
+                }
+            }
+        }
+    }
+
+    pub(crate) fn expand(&self, warnings: TokenStream) -> TokenStream {
+        let krate = self.krate;
+        let n_branches = self.n_branches();
+        let output_enum = &self.output_enum;
+        let else_arms = self.else_arms();
+        let matching = self.matching(&else_arms);
+        let report_declaration = self.select.reports().then(|| {
+            quote_mixed! { let mut __select_report = #krate::CompleteReport::<#n_branches>::new(); }
+        });
+        // Whether any branch is enabled, it outlives polling to tell apart `SelectError`s.
+        let enabled_declaration = self.select.nopanic.map(|_| quote_mixed! { let mut __select_enabled = false; });
+
+        if self.flavor == Flavor::Loop {
+            let declaration = self.declaration();
+            let arming = self.arming();
+            let polling = self.polling();
+            return quote_mixed! {{
+                #warnings
+                #output_enum
+                #report_declaration
+                #declaration
+                loop {
+                    #arming
+                    let mut output = #polling;
+                    #matching
+                }
+            }};
+        }
+
+        if self.flavor == Flavor::Poll {
+            let declaration = self.declaration();
+            let polling = self.polling();
+            // Conditions and futures are evaluated again after `else` clauses, which disable mismatched
+            // branches.
+            let driving = match else_arms.is_empty() {
+                true => quote_mixed! {{
+                    #declaration
+                    #polling
+                }},
+                false => quote_mixed! {
+                    loop {
+                        let output = {
+                            #declaration
+                            #polling
+                        };
+                        let ::core::task::Poll::Ready(output) = output else {
+                            break ::core::task::Poll::Pending;
+                        };
+                        match output {
+                            #(#else_arms)*
+                            output => break ::core::task::Poll::Ready(output),
+                        }
+                    }
+                },
+            };
+            return quote_mixed! {{
+                #warnings
+                #output_enum
+                #enabled_declaration
+                #report_declaration
+                let output = #driving;
+                match output {
+                    ::core::task::Poll::Pending => ::core::task::Poll::Pending,
+                    ::core::task::Poll::Ready(mut output) => ::core::task::Poll::Ready(#matching),
+                }
+            }};
+        }
+
+        let driving = self.driving(&else_arms);
+        // Futures outlive selection to be kept.
+        let Some(keep) = self.select.keep.as_ref() else {
+            let declaration = self.declaration();
+            return quote_mixed! {{
+                #warnings
+                #output_enum
+                #enabled_declaration
+                #report_declaration
+                let mut output = {
+                    #declaration
+                    #driving
+                };
+                #matching
+            }};
+        };
+        let storage = self.storage();
+        let borrowing = self.borrowing();
+        let rearming = self.rearming_declaration();
+        let kept_slots = self.select.branches.iter().enumerate().map(|(i, branch)| branch.future_slot(i));
+        quote_mixed! {{
+            #warnings
+            #output_enum
+            #storage
+            #enabled_declaration
+            #report_declaration
+            let mut output = {
+                #borrowing
+                #rearming
+                #driving
+            };
+            let #keep = (#(#kept_slots,)*);
+            #matching
+        }}
+    }
+}
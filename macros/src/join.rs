@@ -0,0 +1,197 @@
+//! `join!` and `try_join!` built on branches of `select!`.
+
+use quote::{quote_spanned, ToTokens};
+use syn::{Index, Result};
+
+use crate::{Prefixed, Select};
+
+// Clauses and options of `select!` make no sense in joining.
+fn check_join(select: &Select, name: &str) -> Result<()> {
+    let unsupported = |tokens: &dyn ToTokens, what: &str| {
+        Err(syn::Error::new_spanned(tokens, format!("`{name}!`: {what} is not supported")))
+    };
+    if let Some((default, _)) = select.default_clause.as_ref() {
+        return unsupported(default, "`default`");
+    }
+    if let Some((complete, _)) = select.complete_clause.as_ref() {
+        return unsupported(complete, "`complete`");
+    }
+    if let Some(error) = select.error_clause.as_ref() {
+        return unsupported(&error.keyword, "`error`");
+    }
+    if let Some(timeout) = select.timeout.as_ref() {
+        return unsupported(&timeout.keyword, "`timeout`");
+    }
+    if let Some(keep) = select.keep.as_ref() {
+        return unsupported(keep, "`keep`");
+    }
+    if let Some(compact) = select.compact.as_ref() {
+        return unsupported(compact, "`compact`");
+    }
+    if let Some(nopanic) = select.nopanic.as_ref() {
+        return unsupported(nopanic, "`nopanic`");
+    }
+    if let Some((fair, _)) = select.fair.as_ref() {
+        return unsupported(fair, "`fair`");
+    }
+    if let Some((seed, _)) = select.seed.as_ref() {
+        return unsupported(seed, "`seed`");
+    }
+    for branch in select.branches.iter() {
+        if let Some(label) = branch.label.as_ref() {
+            return unsupported(label, "branch label");
+        }
+        if let Some(repeat) = branch.repeat.as_ref() {
+            return unsupported(repeat, "`repeat`");
+        }
+        if let Some(stream) = branch.stream.as_ref() {
+            return unsupported(stream, "`stream`");
+        }
+        if let Some(weight) = branch.weight.as_ref() {
+            return unsupported(&weight.keyword, "`weight`");
+        }
+        if let Some(else_clause) = branch.else_clause.as_ref() {
+            return unsupported(&else_clause.keyword, "`else`");
+        }
+        if let Some(guard) = branch.guard.as_ref() {
+            return unsupported(&guard.keyword, "guard");
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn join_internal(input: proc_macro::TokenStream, trying: bool) -> proc_macro::TokenStream {
+    let Prefixed { krate, inner: select } = match Prefixed::<Select>::parse_input(input) {
+        Ok(prefixed) => prefixed,
+        Err(errors) => return errors,
+    };
+    let callee = match trying {
+        false => quote_mixed! { #krate::join_default },
+        true => quote_mixed! { #krate::try_join_default },
+    };
+    if let Some(expansion) = select.resolve_cfg(callee, quote_mixed! { #krate; }) {
+        return expansion.into();
+    }
+    if let Err(err) = check_join(&select, if trying { "try_join" } else { "join" }) {
+        return err.into_compile_error().into();
+    }
+    let n_branches = select.branches.len();
+    let branch_futures = select.branches.iter().map(|branch| branch.conditional_future());
+    let empty_outputs = select.branches.iter().map(|_| quote_mixed! { ::core::option::Option::None });
+
+    let branch_pollings = select.branches.iter().enumerate().map(|(i, branch)| {
+        let span = branch.span();
+        let index = Index::from(i);
+        let ready = match trying {
+            false => quote_spanned! {span=>
+                ::core::task::Poll::Ready(output) => output,
+            },
+            true => quote_spanned! {span=>
+                ::core::task::Poll::Ready(::core::result::Result::Ok(output)) => output,
+                ::core::task::Poll::Ready(::core::result::Result::Err(err)) => {
+                    return ::core::task::Poll::Ready(::core::result::Result::Err(err));
+                },
+            },
+        };
+        quote_spanned! {span=>
+            #index => {
+                let ::core::option::Option::Some(future) = __join_futures.#index.as_mut() else {
+                    continue;
+                };
+                #[allow(unused_unsafe)]
+                let future = unsafe {
+                    ::core::pin::Pin::new_unchecked(future)
+                };
+                let output = match __join_wakes.poll(#index, cx, |cx| ::core::future::Future::poll(future, cx)) {
+                    #ready
+                    ::core::task::Poll::Pending => {
+                        any_pending = true;
+                        continue;
+                    },
+                };
+                __join_futures.#index = ::core::option::Option::None;
+                __join_outputs.#index = ::core::option::Option::Some(output);
+            }
+        }
+    });
+
+    let completed = match trying {
+        false => quote_mixed! { ::core::task::Poll::Ready(()) },
+        true => quote_mixed! { ::core::task::Poll::Ready(::core::result::Result::Ok(())) },
+    };
+
+    // Disabled branches yield `None`.
+    let branch_results = select.branches.iter().enumerate().map(|(i, branch)| {
+        let index = Index::from(i);
+        let bind = &branch.bind;
+        let clause = &branch.clause;
+        let attrs = clause.arm_attrs();
+        let result = match branch.condition {
+            None => quote_mixed! {
+                {
+                    let #bind = match __join_outputs.#index {
+                        ::core::option::Option::Some(output) => output,
+                        ::core::option::Option::None => ::core::unreachable!("join! encounter incomplete branch"),
+                    };
+                    #clause
+                }
+            },
+            Some(_) => quote_mixed! {
+                match __join_outputs.#index {
+                    ::core::option::Option::Some(output) => {
+                        let #bind = output;
+                        ::core::option::Option::Some(#clause)
+                    },
+                    ::core::option::Option::None => ::core::option::Option::None,
+                }
+            },
+        };
+        quote_mixed! { #attrs #result }
+    });
+
+    let joining = quote_mixed! {
+        ::core::future::poll_fn(|cx| {
+            let mut any_pending = false;
+            for i in 0..#n_branches {
+                match i {
+                    #(#branch_pollings)*
+                    _ => ::core::unreachable!("join! encounter mismatch branch in polling"),
+                }
+            }
+            if any_pending {
+                return ::core::task::Poll::Pending;
+            }
+            #completed
+        }).await
+    };
+
+    let joined = match trying {
+        false => quote_mixed! {
+            {
+                // Shadow it so it won't be moved accidentally.
+                let mut __join_futures = &mut __join_futures;
+                #joining
+            };
+            (#(#branch_results,)*)
+        },
+        true => quote_mixed! {
+            let joined = {
+                // Shadow it so it won't be moved accidentally.
+                let mut __join_futures = &mut __join_futures;
+                #joining
+            };
+            match joined {
+                ::core::result::Result::Err(err) => ::core::result::Result::Err(err),
+                ::core::result::Result::Ok(()) => ::core::result::Result::Ok((#(#branch_results,)*)),
+            }
+        },
+    };
+
+    quote_mixed! {{
+        let mut __join_futures = (#(#branch_futures,)*);
+        let mut __join_outputs = (#(#empty_outputs,)*);
+        let __join_wakes = #krate::__private::WakeSet::<#n_branches, { #krate::__private::wake_words(#n_branches) }>::new();
+        #joined
+    }}
+    .into()
+}
@@ -1,18 +1,58 @@
-//! Procedural macros for `select!`.
+//! Procedural macros for `select!` and `join!`.
+
+/// `quote!` with mixed site spans, so internal variables are invisible to user tokens, which keep
+/// their call site spans.
+macro_rules! quote_mixed {
+    ($($tt:tt)*) => {
+        quote::quote_spanned! {proc_macro2::Span::mixed_site()=> $($tt)*}
+    };
+}
+
+mod control;
+mod expand;
+mod join;
+mod lint;
 
 use proc_macro2::{Span, TokenStream};
-use quote::{format_ident, quote, ToTokens};
+use quote::{format_ident, quote_spanned, ToTokens};
+use syn::buffer::Cursor;
 use syn::parse::{Parse, ParseStream};
-use syn::{Expr, Ident, Index, Pat, Result, Token};
+use syn::spanned::Spanned;
+use syn::{parenthesized, token, Attribute, Expr, Ident, Index, Lifetime, LitInt, Pat, Path, Result, Token, Type};
+
+use crate::expand::Expander;
 
 mod kw {
+    syn::custom_keyword!(biased);
+    syn::custom_keyword!(compact);
     syn::custom_keyword!(complete);
+    syn::custom_keyword!(error);
+    syn::custom_keyword!(fair);
+    syn::custom_keyword!(keep);
+    syn::custom_keyword!(nopanic);
+    syn::custom_keyword!(random);
+    syn::custom_keyword!(repeat);
+    syn::custom_keyword!(round_robin);
+    syn::custom_keyword!(seed);
+    syn::custom_keyword!(stream);
+    syn::custom_keyword!(timeout);
+    syn::custom_keyword!(timer);
+    syn::custom_keyword!(weight);
 }
 
 struct Clause {
+    // Outer attributes of branch or clause except `#[cfg]`s, they are put on generated match arms.
+    attrs: Vec<Attribute>,
     expr: Expr,
 }
 
+impl Clause {
+    fn arm_attrs(&self) -> TokenStream {
+        let attrs = &self.attrs;
+        quote_mixed! { #(#attrs)* }
+    }
+}
+
 impl Parse for Clause {
     fn parse(input: ParseStream<'_>) -> Result<Self> {
         input.parse::<Token![=>]>()?;
@@ -22,7 +62,7 @@ impl Parse for Clause {
         } else if !input.is_empty() {
             input.parse::<Token![,]>()?;
         }
-        Ok(Clause { expr })
+        Ok(Clause { attrs: Vec::new(), expr })
     }
 }
 
@@ -51,18 +91,140 @@ impl ToTokens for Condition {
     }
 }
 
+/// `if guard` on output of branch future.
+struct Guard {
+    keyword: Token![if],
+    expr: Expr,
+}
+
+impl Parse for Guard {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let keyword = input.parse::<Token![if]>()?;
+        let expr = Expr::parse_without_eager_brace(input)?;
+        Ok(Guard { keyword, expr })
+    }
+}
+
+impl ToTokens for Guard {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.expr.to_tokens(tokens)
+    }
+}
+
+struct Weight {
+    keyword: kw::weight,
+    value: u32,
+}
+
+impl Parse for Weight {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        input.parse::<Token![,]>()?;
+        let keyword = input.parse::<kw::weight>()?;
+        let lit = input.parse::<LitInt>()?;
+        let value = lit.base10_parse::<u32>()?;
+        if value == 0 {
+            return Err(syn::Error::new_spanned(lit, "`select!`: weight must be positive"));
+        }
+        Ok(Weight { keyword, value })
+    }
+}
+
+/// `else [continue] [pat => clause]` for outputs mismatching branch pattern.
+struct ElseClause {
+    keyword: Token![else],
+    // Re-enables the branch after mismatch.
+    continuing: Option<Token![continue]>,
+    arm: Option<(Pat, Clause)>,
+}
+
+impl Parse for ElseClause {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let keyword = input.parse::<Token![else]>()?;
+        let continuing = input.parse::<Option<Token![continue]>>()?;
+        let fork = input.fork();
+        let arm = match Pat::parse_multi(&fork).is_ok() && fork.peek(Token![=>]) {
+            true => Some((Pat::parse_multi(input)?, Clause::parse(input)?)),
+            false if continuing.is_some() => {
+                if !input.is_empty() {
+                    input.parse::<Token![,]>()?;
+                }
+                None
+            },
+            false => return Err(input.error("`select!`: expect `continue` or `pat => clause` after `else`")),
+        };
+        Ok(ElseClause { keyword, continuing, arm })
+    }
+}
+
 struct Branch {
+    label: Option<Lifetime>,
     bind: Pat,
     check: Pat,
     future: Expr,
     condition: Option<Condition>,
+    weight: Option<Weight>,
+    guard: Option<Guard>,
     clause: Clause,
+    else_clause: Option<ElseClause>,
+    repeat: Option<kw::repeat>,
+    stream: Option<kw::stream>,
 }
 
 impl Branch {
+    /// Span for generated tokens of this branch to locate errors at its future.
+    fn span(&self) -> Span {
+        Span::mixed_site().located_at(self.future.span())
+    }
+
+    /// Whether future of this branch is rearmed inside select by `repeat` or `else continue`.
+    fn rearmable(&self) -> bool {
+        self.stream.is_none()
+            && (self.repeat.is_some() || self.else_clause.as_ref().is_some_and(|clause| clause.continuing.is_some()))
+    }
+
+    /// Place of future of branch `index`.
+    ///
+    /// Rearmable futures are stored in their own variables. Rearming moves out such a variable, which
+    /// is `None` then, before evaluating future again, so borrows of previous future end there, and
+    /// futures could borrow mutably.
+    fn future_slot(&self, index: usize) -> TokenStream {
+        match self.rearmable() {
+            false => {
+                let index = Index::from(index);
+                quote_mixed! { __select_futures.#index }
+            },
+            true => {
+                let ident = format_ident!("__select_future_{index}", span = Span::mixed_site());
+                quote_mixed! { #ident }
+            },
+        }
+    }
+
     fn conditional_future(&self) -> ConditionalFuture<'_> {
         ConditionalFuture { future: &self.future, condition: self.condition.as_ref() }
     }
+
+    /// Match arm to set `__select_matched` if output in `variant` matches pattern and guard.
+    ///
+    /// The arm is never taken, so it matches against output but not reference to it without moving
+    /// anything out.
+    fn matching(&self, variant: Option<TokenStream>) -> TokenStream {
+        let bind = &self.check;
+        let pat = match variant {
+            None => quote_mixed! { #bind },
+            Some(variant) => quote_mixed! { #variant(#bind) },
+        };
+        let guard = match self.guard.as_ref() {
+            None => quote_mixed! { true },
+            Some(guard) => quote_mixed! { #guard },
+        };
+        quote_mixed! {
+            #pat if {
+                __select_matched = #guard;
+                false
+            } => ::core::unreachable!(),
+        }
+    }
 }
 
 struct ConditionalFuture<'a> {
@@ -74,27 +236,132 @@ impl ToTokens for ConditionalFuture<'_> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let future = self.future;
         match self.condition {
-            None => quote! { ::core::option::Option::Some(#future) },
-            Some(condition) => quote! { if #condition { ::core::option::Option::Some(#future) } else { None } },
+            None => quote_mixed! { ::core::option::Option::Some(#future) },
+            Some(condition) => quote_mixed! { if #condition { ::core::option::Option::Some(#future) } else { None } },
         }
         .to_tokens(tokens);
     }
 }
 
+/// Start branch policy of unbiased select.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+enum Fairness {
+    #[default]
+    Random,
+    RoundRobin,
+}
+
+impl Parse for Fairness {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(kw::random) {
+            input.parse::<kw::random>()?;
+            Ok(Fairness::Random)
+        } else if lookahead.peek(kw::round_robin) {
+            input.parse::<kw::round_robin>()?;
+            Ok(Fairness::RoundRobin)
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
+struct Timeout {
+    keyword: kw::timeout,
+    duration: Expr,
+    clause: Clause,
+}
+
+impl Parse for Timeout {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let keyword = input.parse::<kw::timeout>()?;
+        let content;
+        parenthesized!(content in input);
+        let duration = content.parse::<Expr>()?;
+        let clause = Clause::parse(input)?;
+        Ok(Timeout { keyword, duration, clause })
+    }
+}
+
+struct ErrorClause {
+    keyword: kw::error,
+    pat: Pat,
+    clause: Clause,
+}
+
+impl Parse for ErrorClause {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let keyword = input.parse::<kw::error>()?;
+        let content;
+        parenthesized!(content in input);
+        let pat = Pat::parse_multi(&content)?;
+        let clause = Clause::parse(input)?;
+        Ok(ErrorClause { keyword, pat, clause })
+    }
+}
+
+/// Source tokens of a branch or clause to expand again after resolving its `#[cfg]`s.
+struct Entry {
+    cfgs: Vec<TokenStream>,
+    tokens: TokenStream,
+}
+
+impl ToTokens for Entry {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let cfgs = &self.cfgs;
+        quote_mixed! { #(#[cfg(#cfgs)])* }.to_tokens(tokens);
+        self.tokens.to_tokens(tokens);
+    }
+}
+
+fn tokens_between(begin: Cursor<'_>, end: Cursor<'_>) -> TokenStream {
+    let mut tokens = TokenStream::new();
+    let mut cursor = begin;
+    while cursor != end {
+        let Some((token, next)) = cursor.token_tree() else {
+            break;
+        };
+        tokens.extend([token]);
+        cursor = next;
+    }
+    tokens
+}
+
 #[derive(Default)]
 struct Select {
-    default_clause: Option<Clause>,
-    complete_clause: Option<Clause>,
+    biased: Option<kw::biased>,
+    compact: Option<kw::compact>,
+    fair: Option<(kw::fair, Fairness)>,
+    seed: Option<(kw::seed, Expr)>,
+    timer: Option<(kw::timer, Type)>,
+    keep: Option<Pat>,
+    nopanic: Option<kw::nopanic>,
+    default_clause: Option<(Token![default], Clause)>,
+    complete_clause: Option<(kw::complete, Clause)>,
+    // Pattern of `complete(report) => ...` to bind `CompleteReport`.
+    complete_report: Option<Pat>,
+    timeout: Option<Timeout>,
+    error_clause: Option<ErrorClause>,
+    // Index of branch desugared from `timeout`.
+    timeout_branch: Option<usize>,
+    // Leading branches of futures resolving to `Result` in `try_select!`.
+    fallible: usize,
     branches: Vec<Branch>,
+    // Source tokens of options and clauses to resolve `#[cfg]`s.
+    options: TokenStream,
+    entries: Vec<Entry>,
 }
 
 // This is mainly copied from https://github.com/tokio-rs/tokio/blob/tokio-1.46.1/tokio-macros/src/select.rs#L58
 //
 // See the LICENSE: https://github.com/tokio-rs/tokio/blob/tokio-1.46.1/LICENSE
+//
+// Unlike tokio, patterns are checked against output but not reference to it, so `ref` bindings and
+// reference patterns are kept as is. `ref mut` bindings are weakened to `ref` to not conflict with
+// borrows of guard.
 fn clean_pattern(pat: &mut Pat) {
     match pat {
         syn::Pat::Ident(ident) => {
-            ident.by_ref = None;
             ident.mutability = None;
             if let Some((_at, pat)) = &mut ident.subpat {
                 clean_pattern(&mut *pat);
@@ -105,6 +372,9 @@ fn clean_pattern(pat: &mut Pat) {
                 clean_pattern(case);
             }
         },
+        syn::Pat::Paren(paren) => {
+            clean_pattern(&mut paren.pat);
+        },
         syn::Pat::Slice(slice) => {
             for elem in &mut slice.elems {
                 clean_pattern(elem);
@@ -126,7 +396,6 @@ fn clean_pattern(pat: &mut Pat) {
             }
         },
         syn::Pat::Reference(reference) => {
-            reference.mutability = None;
             clean_pattern(&mut reference.pat);
         },
         syn::Pat::Type(type_pat) => {
@@ -142,193 +411,881 @@ fn to_check_pat(pat: &Pat) -> Pat {
     pat
 }
 
+// `repeat` is a contextual keyword, it could be a function, variable or something else.
+fn peek_repeat(input: ParseStream<'_>) -> bool {
+    input.peek(kw::repeat)
+        && !(input.peek2(token::Paren)
+            || input.peek2(token::Bracket)
+            || input.peek2(Token![.])
+            || input.peek2(Token![::])
+            || input.peek2(Token![?])
+            || input.peek2(Token![!])
+            || input.peek2(Token![,])
+            || input.peek2(Token![=>]))
+}
+
+// `fair` could be a branch pattern, but no branch is terminated by `;`.
+fn peek_fair(input: ParseStream<'_>) -> bool {
+    let fork = input.fork();
+    fork.parse::<kw::fair>().is_ok()
+        && fork.parse::<Token![=]>().is_ok()
+        && fork.parse::<Fairness>().is_ok()
+        && fork.peek(Token![;])
+}
+
+fn peek_seed(input: ParseStream<'_>) -> bool {
+    let fork = input.fork();
+    fork.parse::<kw::seed>().is_ok()
+        && fork.parse::<Token![=]>().is_ok()
+        && fork.parse::<Expr>().is_ok()
+        && fork.peek(Token![;])
+}
+
+fn peek_timer(input: ParseStream<'_>) -> bool {
+    let fork = input.fork();
+    fork.parse::<kw::timer>().is_ok()
+        && fork.parse::<Token![=]>().is_ok()
+        && fork.parse::<Type>().is_ok()
+        && fork.peek(Token![;])
+}
+
+// `timeout(pat) = future => ...` and `error(pat) = future => ...` are branches.
+fn peek_parenthesized_clause(input: ParseStream<'_>, keyword: impl syn::parse::Peek) -> bool {
+    let fork = input.fork();
+    input.peek(keyword) && fork.parse::<Ident>().is_ok() && fork.peek(token::Paren) && {
+        let _ = fork.step(|cursor| match cursor.token_tree() {
+            Some((_, rest)) => Ok(((), rest)),
+            None => Err(cursor.error("no group")),
+        });
+        fork.peek(Token![=>])
+    }
+}
+
+fn peek_keep(input: ParseStream<'_>) -> bool {
+    let fork = input.fork();
+    fork.parse::<kw::keep>().is_ok() && Pat::parse_multi(&fork).is_ok() && fork.peek(Token![;])
+}
+
+/// Error for duplicated `second` of `what`, with a note pointing to `first`.
+fn duplicated(first: &dyn ToTokens, second: &dyn ToTokens, what: &str) -> syn::Error {
+    let mut error = syn::Error::new_spanned(second, format!("`select!`: more than one {what}"));
+    error.combine(syn::Error::new_spanned(first, format!("`select!`: first {what} defined here")));
+    error
+}
+
+impl Select {
+    fn parse_options(&mut self, input: ParseStream<'_>) -> Result<()> {
+        loop {
+            if input.peek(kw::biased) && input.peek2(Token![;]) {
+                let biased = input.parse::<kw::biased>()?;
+                if let Some(first) = self.biased.as_ref() {
+                    return Err(duplicated(first, &biased, "`biased`"));
+                }
+                self.biased = Some(biased);
+            } else if input.peek(kw::nopanic) && input.peek2(Token![;]) {
+                let nopanic = input.parse::<kw::nopanic>()?;
+                if let Some(first) = self.nopanic.as_ref() {
+                    return Err(duplicated(first, &nopanic, "`nopanic`"));
+                }
+                self.nopanic = Some(nopanic);
+            } else if input.peek(kw::compact) && input.peek2(Token![;]) {
+                let compact = input.parse::<kw::compact>()?;
+                if let Some(first) = self.compact.as_ref() {
+                    return Err(duplicated(first, &compact, "`compact`"));
+                }
+                self.compact = Some(compact);
+            } else if peek_fair(input) {
+                let fair = input.parse::<kw::fair>()?;
+                if let Some((first, _)) = self.fair.as_ref() {
+                    return Err(duplicated(first, &fair, "`fair`"));
+                }
+                input.parse::<Token![=]>()?;
+                self.fair = Some((fair, input.parse::<Fairness>()?));
+            } else if peek_seed(input) {
+                let seed = input.parse::<kw::seed>()?;
+                if let Some((first, _)) = self.seed.as_ref() {
+                    return Err(duplicated(first, &seed, "`seed`"));
+                }
+                input.parse::<Token![=]>()?;
+                self.seed = Some((seed, input.parse::<Expr>()?));
+            } else if peek_timer(input) {
+                let timer = input.parse::<kw::timer>()?;
+                if let Some((first, _)) = self.timer.as_ref() {
+                    return Err(duplicated(first, &timer, "`timer`"));
+                }
+                input.parse::<Token![=]>()?;
+                self.timer = Some((timer, input.parse::<Type>()?));
+            } else if peek_keep(input) {
+                let keep = input.parse::<kw::keep>()?;
+                if let Some(first) = self.keep.as_ref() {
+                    return Err(duplicated(first, &keep, "`keep`"));
+                }
+                self.keep = Some(Pat::parse_multi(input)?);
+            } else {
+                return Ok(());
+            }
+            input.parse::<Token![;]>()?;
+        }
+    }
+}
+
 impl Parse for Select {
     fn parse(input: ParseStream<'_>) -> Result<Self> {
         let mut select = Select::default();
+        let begin = input.cursor();
+        select.parse_options(input)?;
+        select.options = tokens_between(begin, input.cursor());
         while !input.is_empty() {
+            let (cfgs, attrs): (Vec<_>, Vec<_>) =
+                input.call(Attribute::parse_outer)?.into_iter().partition(|attr| attr.path().is_ident("cfg"));
+            let cfgs =
+                cfgs.iter().map(|cfg| Ok(cfg.meta.require_list()?.tokens.clone())).collect::<Result<Vec<_>>>()?;
+            // Duplications could be alternatives under `#[cfg]`s, they are checked after resolving.
+            let resolving = !cfgs.is_empty() || select.entries.iter().any(|entry| !entry.cfgs.is_empty());
+            let mut tokens = quote_mixed! { #(#attrs)* };
+            let begin = input.cursor();
             if input.peek(Token![default]) && input.peek2(Token![=>]) {
-                if select.default_clause.is_some() {
-                    return Err(input.error("`select!`: more than one `default` clauses"));
+                let default = input.parse::<Token![default]>()?;
+                if let (Some((first, _)), false) = (select.default_clause.as_ref(), resolving) {
+                    return Err(duplicated(first, &default, "`default` clause"));
+                }
+                let clause = Clause { attrs, ..Clause::parse(input)? };
+                select.default_clause = Some((default, clause));
+            } else if (input.peek(kw::complete) && input.peek2(Token![=>]))
+                || peek_parenthesized_clause(input, kw::complete)
+            {
+                let complete = input.parse::<kw::complete>()?;
+                if let (Some((first, _)), false) = (select.complete_clause.as_ref(), resolving) {
+                    return Err(duplicated(first, &complete, "`complete` clause"));
                 }
-                input.parse::<Token![default]>()?;
-                let clause = Clause::parse(input)?;
-                select.default_clause = Some(clause);
-            } else if input.peek(kw::complete) && input.peek2(Token![=>]) {
-                if select.complete_clause.is_some() {
-                    return Err(input.error("`select!`: more than one `complete` clauses"));
+                let report = match input.peek(token::Paren) {
+                    false => None,
+                    true => {
+                        let content;
+                        parenthesized!(content in input);
+                        Some(Pat::parse_multi(&content)?)
+                    },
+                };
+                let clause = Clause { attrs, ..Clause::parse(input)? };
+                select.complete_clause = Some((complete, clause));
+                select.complete_report = report;
+            } else if peek_parenthesized_clause(input, kw::error) {
+                let mut error = input.parse::<ErrorClause>()?;
+                if let (Some(first), false) = (select.error_clause.as_ref(), resolving) {
+                    return Err(duplicated(&first.keyword, &error.keyword, "`error` clause"));
                 }
-                input.parse::<kw::complete>()?;
-                let clause = Clause::parse(input)?;
-                select.complete_clause = Some(clause);
+                error.clause.attrs = attrs;
+                select.error_clause = Some(error);
+            } else if peek_parenthesized_clause(input, kw::timeout) {
+                let mut timeout = input.parse::<Timeout>()?;
+                if let (Some(first), false) = (select.timeout.as_ref(), resolving) {
+                    return Err(duplicated(&first.keyword, &timeout.keyword, "`timeout` clause"));
+                }
+                timeout.clause.attrs = attrs;
+                select.timeout = Some(timeout);
             } else {
+                let label = if input.peek(Lifetime) && input.peek2(Token![:]) {
+                    let label = input.parse::<Lifetime>()?;
+                    input.parse::<Token![:]>()?;
+                    if let (Some(first), false) = (
+                        select
+                            .branches
+                            .iter()
+                            .find_map(|branch| branch.label.as_ref().filter(|first| **first == label)),
+                        resolving,
+                    ) {
+                        return Err(duplicated(first, &label, &format!("branch labeled `{label}`")));
+                    }
+                    Some(label)
+                } else {
+                    None
+                };
                 let bind = Pat::parse_multi(input)?;
                 input.parse::<Token![=]>()?;
+                let repeat = if peek_repeat(input) { Some(input.parse::<kw::repeat>()?) } else { None };
+                let stream = if input.peek(kw::stream) && input.peek2(Token![in]) {
+                    let stream = input.parse::<kw::stream>()?;
+                    input.parse::<Token![in]>()?;
+                    Some(stream)
+                } else {
+                    None
+                };
                 let future = input.parse::<Expr>()?;
-                let condition = if input.peek(Token![,]) { Some(input.parse::<Condition>()?) } else { None };
-                let clause = Clause::parse(input)?;
+                let condition = if input.peek(Token![,]) && !input.peek2(kw::weight) {
+                    Some(input.parse::<Condition>()?)
+                } else {
+                    None
+                };
+                let weight = if input.peek(Token![,]) { Some(input.parse::<Weight>()?) } else { None };
+                let guard = if input.peek(Token![if]) { Some(input.parse::<Guard>()?) } else { None };
+                let clause = Clause { attrs, ..Clause::parse(input)? };
+                let else_clause = if input.peek(Token![else]) { Some(input.parse::<ElseClause>()?) } else { None };
                 let check = to_check_pat(&bind);
-                select.branches.push(Branch { bind, check, future, condition, clause });
+                select.branches.push(Branch {
+                    label,
+                    bind,
+                    check,
+                    future,
+                    condition,
+                    weight,
+                    guard,
+                    clause,
+                    else_clause,
+                    repeat,
+                    stream,
+                });
             }
+            tokens.extend(tokens_between(begin, input.cursor()));
+            select.entries.push(Entry { cfgs, tokens });
         }
-        match (select.branches.is_empty(), select.complete_clause.is_some(), select.default_clause.is_some()) {
-            (true, false, false) => return Err(input.error("`select!`: no branch")),
-            (true, false, true) => return Err(input.error("`select!`: no branch except `default`")),
-            (true, true, false) => return Err(input.error("`select!`: no branch except `complete`")),
-            (true, true, true) => return Err(input.error("`select!`: no branch except `default` and `complete`")),
+        let no_branch = select.branches.is_empty() && select.timeout.is_none();
+        match (no_branch, select.complete_clause.as_ref(), select.default_clause.as_ref()) {
+            (true, None, None) => return Err(syn::Error::new(Span::call_site(), "`select!`: no branch")),
+            (true, None, Some((default, _))) => {
+                return Err(syn::Error::new_spanned(default, "`select!`: no branch except `default`"))
+            },
+            (true, Some((complete, _)), None) => {
+                return Err(syn::Error::new_spanned(complete, "`select!`: no branch except `complete`"))
+            },
+            (true, Some((complete, _)), Some((default, _))) => {
+                let mut error =
+                    syn::Error::new_spanned(complete, "`select!`: no branch except `default` and `complete`");
+                error.combine(syn::Error::new_spanned(default, "`select!`: `default` defined here"));
+                return Err(error);
+            },
             (_, _, _) => {},
         };
+        if let (Some(timer), None) = (select.timer.as_ref(), select.timeout.as_ref()) {
+            return Err(syn::Error::new_spanned(timer.0, "`select!`: `timer` without `timeout`"));
+        }
         Ok(select)
     }
 }
 
-fn define_output_enum(ident: &Ident, branches: usize, span: Span) -> (Vec<Ident>, TokenStream) {
-    let type_names: Vec<_> = (0..branches).map(|i| format_ident!("T{i}", span = span)).collect();
-    let branch_names: Vec<_> = (0..branches).map(|i| format_ident!("_{i}", span = span)).collect();
-    let output_enum = quote! {
-        enum #ident<#(#type_names,)*> {
-            Completed,
-            WouldBlock,
-            #(
-                #branch_names(#type_names),
-            )*
+impl Select {
+    /// Expands to `callee!` with and without the first branch or clause under `#[cfg]`s in arms of
+    /// `match` under these `#[cfg]`s, so rustc resolves `#[cfg]`s one by one. `header` precedes
+    /// options in input of `callee!`.
+    fn resolve_cfg(&self, callee: TokenStream, header: TokenStream) -> Option<TokenStream> {
+        let index = self.entries.iter().position(|entry| !entry.cfgs.is_empty())?;
+        let cfgs = &self.entries[index].cfgs;
+        let options = &self.options;
+        let enabled = self.entries.iter().enumerate().map(|(i, entry)| match i == index {
+            true => entry.tokens.clone(),
+            false => entry.to_token_stream(),
+        });
+        let disabled = self.entries.iter().enumerate().filter(|(i, _)| *i != index).map(|(_, entry)| entry);
+        Some(quote_mixed! {{
+            match () {
+                #[cfg(all(#(#cfgs),*))]
+                () => #callee! { #header #options #(#enabled)* },
+                #[cfg(not(all(#(#cfgs),*)))]
+                () => #callee! { #header #options #(#disabled)* },
+            }
+        }})
+    }
+
+    /// Matches patterns against `Ok` values of futures in `try_select!`, and wraps clauses in `Ok`
+    /// unless there is `error(pat) => clause` to handle errors.
+    fn desugar_trying(&mut self, fallible: usize) {
+        self.fallible = fallible;
+        let wrapping = self.error_clause.is_none();
+        let wrap = |clause: &mut Clause| {
+            let expr = &clause.expr;
+            // Diverging clauses, say `unreachable!()`, are common and should not warn about `Ok(_)`.
+            clause.expr = syn::parse_quote! {{{
+                #![allow(unreachable_code)]
+                ::core::result::Result::Ok(#expr)
+            }}};
         };
-    };
-    (branch_names, output_enum)
-}
+        for (i, branch) in self.branches.iter_mut().enumerate() {
+            if i < fallible {
+                let (bind, check) = (&branch.bind, &branch.check);
+                branch.bind = syn::parse_quote! { ::core::result::Result::Ok(#bind) };
+                branch.check = syn::parse_quote! { ::core::result::Result::Ok(#check) };
+            }
+            // Clauses of `repeat` branches are evaluated to `()` inside select.
+            if wrapping && branch.repeat.is_none() {
+                wrap(&mut branch.clause);
+            }
+        }
+        if wrapping {
+            self.default_clause
+                .iter_mut()
+                .map(|(_, clause)| clause)
+                .chain(self.complete_clause.iter_mut().map(|(_, clause)| clause))
+                .for_each(wrap);
+        }
+    }
 
-fn select_internal(input: proc_macro::TokenStream, biased: bool) -> proc_macro::TokenStream {
-    let select = syn::parse_macro_input!(input as Select);
-    let span = Span::call_site();
-    let output_ident = Ident::new("__SelectOutput", span);
-    let (branch_names, output_enum) = define_output_enum(&output_ident, select.branches.len(), span);
+    /// Wraps clauses in `Ok` for `nopanic`, so exhaustion without `default` nor `complete` could
+    /// evaluate to `Err(SelectError)`.
+    fn desugar_nopanic(&mut self, krate: &Path) {
+        if self.nopanic.is_none() {
+            return;
+        }
+        let wrap = |clause: &mut Clause| {
+            let expr = &clause.expr;
+            clause.expr = syn::parse_quote! {{{
+                #![allow(unreachable_code)]
+                ::core::result::Result::Ok::<_, #krate::SelectError>(#expr)
+            }}};
+        };
+        self.branches.iter_mut().filter(|branch| branch.repeat.is_none()).for_each(|branch| wrap(&mut branch.clause));
+        self.default_clause
+            .iter_mut()
+            .map(|(_, clause)| clause)
+            .chain(self.complete_clause.iter_mut().map(|(_, clause)| clause))
+            .chain(self.error_clause.iter_mut().map(|error| &mut error.clause))
+            .for_each(wrap);
+    }
 
-    let branch_futures = select.branches.iter().map(|branch| branch.conditional_future());
+    /// Handler of `complete` clause with `report` bound to its pattern.
+    fn complete_handler(&self, clause: &Clause) -> TokenStream {
+        match self.complete_report.as_ref() {
+            None => quote_mixed! { #clause },
+            Some(pat) => quote_mixed! {{
+                let #pat = __select_report;
+                #clause
+            }},
+        }
+    }
 
-    let select_futures_declartion = quote! {
-        let mut __select_futures = (#(#branch_futures,)*);
-        // Shadow it so it won't be moved accidentally.
-        let mut __select_futures = &mut __select_futures;
-    };
+    /// Whether to track how branches ended, either for `complete(report) => ...` or for panic on
+    /// exhaustion.
+    fn reports(&self) -> bool {
+        self.complete_report.is_some()
+            || (self.complete_clause.is_none() && self.default_clause.is_none() && self.nopanic.is_none())
+    }
 
-    let default_handler = match select.default_clause.as_ref() {
-        None => quote! { ::core::unreachable!("not in unblocking mode") },
-        Some(clause) => quote! { #clause },
-    };
+    /// Records how branch `index` ended for `complete(report) => ...` or panic on exhaustion.
+    fn reporting(&self, krate: &Path, index: &Index, end: &str) -> TokenStream {
+        if !self.reports() {
+            return quote_mixed! {};
+        }
+        let end = Ident::new(end, Span::mixed_site());
+        quote_mixed! { __select_report.set(#index, #krate::BranchEnd::#end); }
+    }
 
-    let complete_handler = match select.complete_clause.as_ref() {
-        None => quote! {
-            ::core::panic!("all branches are disabled or completed and there is no `default` nor `complete`")
-        },
-        Some(clause) => quote! { #clause },
-    };
+    /// Evaluates to `Err(SelectError)` on exhaustion in `nopanic` mode or panics otherwise.
+    fn exhausting(&self, krate: &Path) -> TokenStream {
+        match self.nopanic {
+            // `default` resorts on exhaustion, so this is unreachable.
+            None if !self.reports() => quote_mixed! {
+                ::core::panic!("all branches are disabled or completed and there is no `default` nor `complete`")
+            },
+            None => {
+                // `line!()` spanned at user tokens reports lines of branches but not `select!`.
+                let sources = self.branches.iter().map(|branch| {
+                    let future = &branch.future;
+                    let condition = match branch.condition.as_ref() {
+                        None => quote_mixed! { ::core::option::Option::None },
+                        Some(condition) => {
+                            quote_mixed! { ::core::option::Option::Some(::core::stringify!(#condition)) }
+                        },
+                    };
+                    let line = quote_spanned! {future.span()=> ::core::line!() };
+                    quote_mixed! {
+                        #krate::__private::BranchSource {
+                            future: ::core::stringify!(#future),
+                            condition: #condition,
+                            file: ::core::file!(),
+                            line: #line,
+                        }
+                    }
+                });
+                quote_mixed! { #krate::__private::exhausted(&__select_report, &[#(#sources),*]) }
+            },
+            Some(_) => quote_mixed! {
+                ::core::result::Result::Err(match __select_enabled {
+                    false => #krate::SelectError::Disabled,
+                    true => #krate::SelectError::Completed,
+                })
+            },
+        }
+    }
 
-    let (pending_declaration, pending_assignment, pending_check) =
-        match select.complete_clause.is_some() || select.default_clause.is_none() {
-            true => (
-                quote! {
-                    let mut any_pending = false;
-                },
-                quote! {
-                    any_pending = true;
+    /// Match arm to pass errors of fallible branch `index` through pattern checking.
+    fn error_passing(&self, index: usize, variant: TokenStream) -> TokenStream {
+        match index < self.fallible {
+            false => quote_mixed! {},
+            true => quote_mixed! { #variant(::core::result::Result::Err(_)) => __select_matched = true, },
+        }
+    }
+
+    /// Match arm for errors from fallible branches.
+    fn error_arm(&self, variants: &[TokenStream]) -> TokenStream {
+        if self.fallible == 0 {
+            return quote_mixed! {};
+        }
+        let variants = &variants[..self.fallible];
+        let attrs = self.error_clause.as_ref().map(|error| error.clause.arm_attrs());
+        let handler = match self.error_clause.as_ref() {
+            None if self.nopanic.is_some() => quote_mixed! {
+                ::core::result::Result::Ok(::core::result::Result::Err(__select_error))
+            },
+            None => quote_mixed! { ::core::result::Result::Err(__select_error) },
+            Some(ErrorClause { pat, clause, .. }) => quote_mixed! {{
+                let #pat = __select_error;
+                #clause
+            }},
+        };
+        quote_mixed! {
+            #attrs
+            #(#variants(::core::result::Result::Err(__select_error)))|* => #handler,
+        }
+    }
+
+    /// Maps `output` to `SelectedBranch` for `select_indexed!`.
+    fn indexing(
+        &self,
+        krate: &Path,
+        variants: &[TokenStream],
+        default: TokenStream,
+        complete: TokenStream,
+    ) -> TokenStream {
+        let complete_branch = match (self.complete_clause.as_ref(), self.default_clause.as_ref()) {
+            (None, Some(_)) => quote_mixed! { #krate::SelectedBranch::Default },
+            _ => quote_mixed! { #krate::SelectedBranch::Complete },
+        };
+        let arms = variants.iter().zip(self.branches.iter()).enumerate().map(|(index, (variant, branch))| {
+            let branch = match (self.timeout_branch == Some(index), branch.label.as_ref()) {
+                (true, _) => quote_mixed! { #krate::SelectedBranch::Timeout },
+                (false, None) => quote_mixed! {
+                    #krate::SelectedBranch::Future { index: #index, label: ::core::option::Option::None }
                 },
-                quote! {
-                    if !any_pending {
-                        return ::core::task::Poll::Ready(__SelectOutput::Completed);
+                (false, Some(label)) => {
+                    let label = label.ident.to_string();
+                    quote_mixed! {
+                        #krate::SelectedBranch::Future { index: #index, label: ::core::option::Option::Some(#label) }
                     }
                 },
-            ),
-            false => (quote! {}, quote! {}, quote! {}),
+            };
+            quote_mixed! { #variant(_) => #branch, }
+        });
+        quote_mixed! {
+            #[allow(unreachable_patterns)]
+            match &output {
+                #default => #krate::SelectedBranch::Default,
+                #complete => #complete_branch,
+                #(#arms)*
+                _ => ::core::unreachable!("select! fail to index selected branch"),
+            }
+        }
+    }
+
+    /// Turns `timeout(duration) => clause` into branch `_ = sleep(duration) => clause` at last.
+    fn desugar_timeout(&mut self, krate: &Path) {
+        let Some(Timeout { keyword, duration, clause }) = self.timeout.take() else {
+            return;
         };
-    let default_clause = match select.default_clause.is_some() {
-        true => quote! { ::core::task::Poll::Ready(__SelectOutput::WouldBlock) },
-        false => quote! { ::core::task::Poll::Pending },
-    };
+        let timer = match self.timer.as_ref() {
+            None => quote_mixed! { #krate::DefaultTimer },
+            Some((_, timer)) => quote_mixed! { #timer },
+        };
+        let future = syn::parse_quote_spanned! { keyword.span => <#timer as #krate::Timer>::sleep(#duration) };
+        let bind: Pat = syn::parse_quote! { _ };
+        self.timeout_branch = Some(self.branches.len());
+        self.branches.push(Branch {
+            label: None,
+            check: bind.clone(),
+            bind,
+            future,
+            condition: None,
+            weight: None,
+            guard: None,
+            clause,
+            else_clause: None,
+            repeat: None,
+            stream: None,
+        });
+    }
+}
+
+/// Macro input prefixed with `$crate;` by `macro_rules!` front end.
+struct Prefixed<T> {
+    krate: Path,
+    inner: T,
+}
+
+impl<T: Parse> Parse for Prefixed<T> {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let krate = input.call(Path::parse_mod_style)?;
+        input.parse::<Token![;]>()?;
+        let inner = input.parse()?;
+        Ok(Prefixed { krate, inner })
+    }
+}
+
+impl<T: Parse> Prefixed<T> {
+    /// Parses macro input, or returns errors wrapped in block so combined errors expand to an
+    /// expression.
+    fn parse_input(input: proc_macro::TokenStream) -> std::result::Result<Self, proc_macro::TokenStream> {
+        syn::parse(input).map_err(|err| {
+            let errors = err.into_compile_error();
+            quote_mixed! {{ #errors }}.into()
+        })
+    }
+}
+
+/// `cx, ...` of `poll_select!`.
+struct Contextual {
+    cx: Expr,
+    select: Select,
+}
 
-    let (biased_start, biased_branch) = match biased {
-        true => (quote! {}, quote! { let branch = i; }),
-        false => (
-            quote! {
-                let start = (&__select_futures as *const _ as usize) >> 3;
+impl Parse for Contextual {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let cx = input.parse::<Expr>()?;
+        input.parse::<Token![,]>()?;
+        let select = input.parse::<Select>()?;
+        Ok(Contextual { cx, select })
+    }
+}
+
+// Futures of `poll_select!` are evaluated in every poll, so there is nothing to keep or rearm. Stored
+// futures could be disabled only by callers, so mismatches of them must be handed to `else` clauses.
+fn check_polling(select: &Select) -> Result<()> {
+    let unsupported = |tokens: &dyn ToTokens, what: &str| {
+        Err(syn::Error::new_spanned(tokens, format!("`poll_select!`: {what} is not supported")))
+    };
+    if let Some(keep) = select.keep.as_ref() {
+        return unsupported(keep, "`keep`");
+    }
+    if let Some(compact) = select.compact.as_ref() {
+        return unsupported(compact, "`compact`");
+    }
+    if let Some(timeout) = select.timeout.as_ref() {
+        return unsupported(&timeout.keyword, "`timeout`");
+    }
+    // Generator would be seeded again in every poll, so it would start from the same branch.
+    if let Some((seed, _)) = select.seed.as_ref() {
+        return unsupported(seed, "`seed`");
+    }
+    for branch in select.branches.iter() {
+        if let Some(repeat) = branch.repeat.as_ref() {
+            return unsupported(repeat, "`repeat`");
+        }
+        match branch.else_clause.as_ref() {
+            Some(ElseClause { continuing: Some(continuing), .. }) => {
+                return unsupported(continuing, "`else continue`");
             },
-            quote! {
-                #[allow(clippy::modulo_one)]
-                let branch = (start +i ) % BRANCHES;
+            None if branch.stream.is_none() => {
+                if let Some(guard) = branch.guard.as_ref() {
+                    return unsupported(&guard.keyword, "guard without `else`");
+                }
             },
-        ),
+            _ => {},
+        }
+    }
+    Ok(())
+}
+
+/// Defines output enum with variant `_i` for selected output and `Unmatched{i}` for mismatched
+/// output of branch `i` with `else` clause.
+fn define_output_enum(ident: &Ident, branches: &[Branch], span: Span) -> (Vec<Ident>, Vec<Ident>, TokenStream) {
+    let type_names: Vec<_> = (0..branches.len()).map(|i| format_ident!("T{i}", span = span)).collect();
+    let branch_names: Vec<_> = (0..branches.len()).map(|i| format_ident!("_{i}", span = span)).collect();
+    let unmatched_names: Vec<_> = (0..branches.len()).map(|i| format_ident!("Unmatched{i}", span = span)).collect();
+    let unmatched_variants =
+        branches.iter().enumerate().filter(|(_, branch)| branch.else_clause.is_some()).map(|(i, _)| {
+            let (name, type_name) = (&unmatched_names[i], &type_names[i]);
+            quote_mixed! { #name(#type_name), }
+        });
+    let output_enum = quote_mixed! {
+        enum #ident<#(#type_names,)*> {
+            Completed,
+            WouldBlock,
+            #(
+                #branch_names(#type_names),
+            )*
+            #(#unmatched_variants)*
+        };
     };
+    (branch_names, unmatched_names, output_enum)
+}
 
-    let branch_handlers = select.branches.iter().map(|branch| &branch.clause);
+// Maximum number of branches `async_select::Select` supports.
+const COMPACT_BRANCHES: usize = 64;
+
+// Expands to `async_select::Select` to share polling code among selects.
+fn select_compact(krate: &Path, select: &Select, flavor: Flavor) -> Result<TokenStream> {
+    if let Some(keep) = select.keep.as_ref() {
+        return Err(syn::Error::new_spanned(keep, "`select!`: `keep` is not supported in `compact`"));
+    }
+    if let Some(branch) = select.branches.iter().find(|branch| branch.repeat.is_some()) {
+        return Err(syn::Error::new_spanned(branch.repeat, "`select!`: `repeat` is not supported in `compact`"));
+    }
+    if let Some(branch) = select.branches.iter().find(|branch| branch.stream.is_some()) {
+        return Err(syn::Error::new_spanned(branch.stream, "`select!`: `stream` is not supported in `compact`"));
+    }
+    if let Some(weight) = select.branches.iter().find_map(|branch| branch.weight.as_ref()) {
+        return Err(syn::Error::new_spanned(weight.keyword, "`select!`: `weight` is not supported in `compact`"));
+    }
+    if let Some(else_clause) = select.branches.iter().find_map(|branch| branch.else_clause.as_ref()) {
+        return Err(syn::Error::new_spanned(else_clause.keyword, "`select!`: `else` is not supported in `compact`"));
+    }
+    if let Some((fair, Fairness::RoundRobin)) = select.fair {
+        return Err(syn::Error::new_spanned(fair, "`select!`: `fair = round_robin` is not supported in `compact`"));
+    }
+    if select.branches.len() > COMPACT_BRANCHES {
+        return Err(syn::Error::new_spanned(
+            select.compact,
+            format!("`select!`: `compact` supports at most {COMPACT_BRANCHES} branches"),
+        ));
+    }
+    let branch_names: Vec<_> = (0..select.branches.len()).map(|i| format_ident!("_{i}")).collect();
+    let branch_variants: Vec<_> = branch_names.iter().map(|name| quote_mixed! { #krate::Selected::#name }).collect();
+    let error_arm = select.error_arm(&branch_variants);
+    let branch_futures = select.branches.iter().map(|branch| branch.conditional_future());
+    let branch_matchings = select
+        .branches
+        .iter()
+        .zip(branch_variants.iter())
+        .map(|(branch, variant)| branch.matching(Some(variant.clone())));
+    let error_passings =
+        branch_variants.iter().enumerate().map(|(i, variant)| select.error_passing(i, variant.clone()));
     let branch_bindings = select.branches.iter().map(|branch| &branch.bind);
-    let branch_binding_checks = select.branches.iter().map(|branch| &branch.check);
-
-    let n_branches = select.branches.len();
-    let branch_indices = (0..n_branches).map(Index::from);
-
-    quote! {{
-        #output_enum
-        const BRANCHES: usize = #n_branches;
-        let mut output = {
-            #select_futures_declartion
-            ::core::future::poll_fn(|cx| {
-                #biased_start
-                #pending_declaration
-                for i in 0..BRANCHES {
-                    #biased_branch
-                    match branch {
-                        #(
-                            #branch_indices => {
-                                let ::core::option::Option::Some(future) = __select_futures.#branch_indices.as_mut() else {
-                                    continue;
-                                };
-                                #[allow(unused_unsafe)]
-                                let future = unsafe {
-                                    ::core::pin::Pin::new_unchecked(future)
-                                };
-                                let mut output = match ::core::future::Future::poll(
-                                    future,
-                                    cx,
-                                ) {
-                                    ::core::task::Poll::Ready(output) => output,
-                                    ::core::task::Poll::Pending => {
-                                        #pending_assignment
-                                        continue;
-                                    },
-                                };
-                                __select_futures.#branch_indices = ::core::option::Option::None;
-                                #[allow(unreachable_patterns)]
-                                #[allow(unused_variables)]
-                                match &output {
-                                    #branch_binding_checks => {},
-                                    _ => continue,
-                                };
-                                return ::core::task::Poll::Ready(__SelectOutput::#branch_names(output));
-                            }
-                        )*
-                            _ => ::core::unreachable!("select! encounter mismatch branch in polling"),
-                    }
-                }
-                #pending_check
-                #default_clause
-            }).await
-        };
+    let branch_attrs = select.branches.iter().map(|branch| branch.clause.arm_attrs());
+    let branch_handlers = select.branches.iter().map(|branch| &branch.clause);
+    let biased = select.biased.map(|_| quote_mixed! { .biased() });
+    let seed = select.seed.as_ref().map(|(_, seed)| quote_mixed! { .seed(#seed) });
+    let nonblocking = select.default_clause.as_ref().map(|_| quote_mixed! { .nonblocking() });
+    let default_handler = match select.default_clause.as_ref() {
+        None => quote_mixed! { ::core::unreachable!("not in unblocking mode") },
+        Some((_, clause)) => quote_mixed! { #clause },
+    };
+    let default_attrs = select.default_clause.as_ref().map(|(_, clause)| clause.arm_attrs());
+    let (complete_attrs, complete_handler) = match (select.complete_clause.as_ref(), select.default_clause.as_ref()) {
+        (Some((_, clause)), _) => (clause.arm_attrs(), select.complete_handler(clause)),
+        (None, Some((_, clause))) => (clause.arm_attrs(), quote_mixed! { #clause }),
+        (None, None) => (quote_mixed! {}, select.exhausting(krate)),
+    };
+    // Branches are never rearmed in `compact`, so they are enabled only if they are armed initially.
+    let enabling = select.nopanic.map(|_| {
+        let indices = (0..select.branches.len()).map(Index::from);
+        quote_mixed! {
+            let __select_enabled = #(__select_futures.#indices.is_some())||*;
+        }
+    });
+    let select_matching = quote_mixed! {
         match output {
-            __SelectOutput::WouldBlock => #default_handler,
-            __SelectOutput::Completed => #complete_handler,
+            #default_attrs
+            #krate::Selected::Default => #default_handler,
+            #complete_attrs
+            #krate::Selected::Complete => #complete_handler,
             #(
-                __SelectOutput::#branch_names(#branch_bindings) => #branch_handlers,
+                #branch_attrs
+                #krate::Selected::#branch_names(#branch_bindings) => #branch_handlers,
             )*
+            #error_arm
             #[allow(unreachable_patterns)] // In case of refutable patterns in branches
             _ => ::core::unreachable!("select! fail to pattern match"),
         }
-    }}.into()
+    };
+    let select_matching = match flavor {
+        Flavor::Indexed => {
+            let default = quote_mixed! { #krate::Selected::Default };
+            let complete = quote_mixed! { #krate::Selected::Complete };
+            let indexing = select.indexing(krate, &branch_variants, default, complete);
+            quote_mixed! { (#indexing, #select_matching) }
+        },
+        _ => select_matching,
+    };
+    // Branches are disabled unless they are completed with mismatched outputs.
+    let (report_declaration, mismatch_reporting) = match select.reports() {
+        false => (quote_mixed! {}, quote_mixed! {}),
+        true => {
+            let n_branches = select.branches.len();
+            let reportings = branch_variants.iter().enumerate().map(|(i, variant)| {
+                let reporting = select.reporting(krate, &Index::from(i), "Mismatched");
+                quote_mixed! { #variant(_) => { #reporting }, }
+            });
+            (quote_mixed! { let mut __select_report = #krate::CompleteReport::<#n_branches>::new(); }, quote_mixed! {
+                if !__select_matched {
+                    match &output {
+                        #(#reportings)*
+                        _ => {},
+                    }
+                }
+            })
+        },
+    };
+    Ok(quote_mixed! {{
+        let __select_futures = (#(#branch_futures,)*);
+        #enabling
+        #report_declaration
+        let output = #krate::__private::select_filtered(
+            #krate::Select::conditional(__select_futures) #biased #seed #nonblocking,
+            |output| {
+                let mut __select_matched = false;
+                #[allow(unreachable_patterns)]
+                #[allow(unused_variables)]
+                #[allow(unused_mut)]
+                match output {
+                    #(#branch_matchings)*
+                    #(#error_passings)*
+                    #krate::Selected::Default | #krate::Selected::Complete => __select_matched = true,
+                    _ => {},
+                };
+                #mismatch_reporting
+                __select_matched.then_some(output)
+            },
+        )
+        .await;
+        #select_matching
+    }})
+}
+
+/// Flavours of `select!` sharing parsing and code generation.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Flavor {
+    /// `select!`.
+    Select,
+    /// `select_indexed!` evaluating to executed branch along with clause value.
+    Indexed,
+    /// `select_loop!` keeping futures alive across iterations.
+    Loop,
+    /// `try_select!` propagating errors of futures.
+    Try,
+    /// `poll_select!` polling with given context.
+    Poll,
+}
+
+impl Flavor {
+    /// Proc macro expanding this flavour.
+    fn callee(self, krate: &Path) -> TokenStream {
+        match self {
+            Flavor::Select => quote_mixed! { #krate::select_default },
+            Flavor::Indexed => quote_mixed! { #krate::select_indexed_default },
+            Flavor::Loop => quote_mixed! { #krate::select_loop_default },
+            Flavor::Try => quote_mixed! { #krate::try_select_default },
+            Flavor::Poll => quote_mixed! { #krate::poll_select_default },
+        }
+    }
+}
+
+/// Rejects options and clauses unsupported in `flavor` or conflicting with each other.
+fn check_select(select: &Select, flavor: Flavor) -> Result<()> {
+    if flavor == Flavor::Poll {
+        check_polling(select)?;
+    }
+    if let (Some(_), Some(timeout)) = (select.keep.as_ref(), select.timeout.as_ref()) {
+        return Err(syn::Error::new_spanned(timeout.keyword, "`select!`: `timeout` is not supported with `keep`"));
+    }
+    if let (false, Some(error)) = (flavor == Flavor::Try, select.error_clause.as_ref()) {
+        return Err(syn::Error::new_spanned(
+            error.keyword,
+            "`select!`: `error` clause is only supported in `try_select!`",
+        ));
+    }
+    match flavor {
+        Flavor::Loop => {
+            if let Some(keep) = select.keep.as_ref() {
+                return Err(syn::Error::new_spanned(
+                    keep,
+                    "`select_loop!`: `keep` is not supported as futures are kept in loop",
+                ));
+            }
+            if let Some(nopanic) = select.nopanic.as_ref() {
+                return Err(syn::Error::new_spanned(nopanic, "`select_loop!`: `nopanic` is not supported"));
+            }
+            if let Some(compact) = select.compact.as_ref() {
+                return Err(syn::Error::new_spanned(compact, "`select_loop!`: `compact` is not supported"));
+            }
+        },
+        // Clauses in `select_loop!` are meant to control its loop.
+        _ => control::check(select)?,
+    }
+    let biased = select.biased.is_some();
+    if let (true, Some((fair, _))) = (biased, select.fair) {
+        return Err(syn::Error::new_spanned(fair, "`select!`: `fair` conflicts with `biased`"));
+    }
+    if let (true, Some((seed, _))) = (biased, select.seed.as_ref()) {
+        return Err(syn::Error::new_spanned(seed, "`select!`: `seed` conflicts with `biased`"));
+    }
+    let weight = select.branches.iter().find_map(|branch| branch.weight.as_ref());
+    if let (true, Some(weight)) = (biased, weight) {
+        return Err(syn::Error::new_spanned(weight.keyword, "`select!`: `weight` conflicts with `biased`"));
+    }
+    if let (Some((fair, Fairness::RoundRobin)), Some(_)) = (select.fair, weight) {
+        return Err(syn::Error::new_spanned(fair, "`select!`: `weight` conflicts with `fair = round_robin`"));
+    }
+    if let (Some((fair, Fairness::RoundRobin)), Some(_)) = (select.fair, select.seed.as_ref()) {
+        return Err(syn::Error::new_spanned(fair, "`select!`: `seed` conflicts with `fair = round_robin`"));
+    }
+    Ok(())
+}
+
+fn select_internal(input: proc_macro::TokenStream, flavor: Flavor) -> proc_macro::TokenStream {
+    let (krate, cx, mut select) = match flavor {
+        Flavor::Poll => match Prefixed::<Contextual>::parse_input(input) {
+            Ok(Prefixed { krate, inner: Contextual { cx, select } }) => (krate, Some(cx), select),
+            Err(errors) => return errors,
+        },
+        _ => match Prefixed::<Select>::parse_input(input) {
+            Ok(Prefixed { krate, inner }) => (krate, None, inner),
+            Err(errors) => return errors,
+        },
+    };
+    let header = match cx.as_ref() {
+        None => quote_mixed! { #krate; },
+        Some(cx) => quote_mixed! { #krate; #cx, },
+    };
+    if let Some(expansion) = select.resolve_cfg(flavor.callee(&krate), header) {
+        return expansion.into();
+    }
+    if let Err(err) = check_select(&select, flavor) {
+        return err.into_compile_error().into();
+    }
+    let warnings = lint::lint(&select, flavor == Flavor::Loop);
+    let fallible = select.branches.len();
+    select.desugar_timeout(&krate);
+    if flavor == Flavor::Try {
+        select.desugar_trying(fallible);
+    }
+    select.desugar_nopanic(&krate);
+    match select.compact {
+        None => Expander::new(&krate, cx.as_ref(), &select, flavor).expand(warnings),
+        Some(_) => match select_compact(&krate, &select, flavor) {
+            Ok(expansion) => quote_mixed! {{ #warnings #expansion }},
+            Err(err) => err.into_compile_error(),
+        },
+    }
+    .into()
 }
 
 #[proc_macro]
 pub fn select_default(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    select_internal(input, false)
+    select_internal(input, Flavor::Select)
+}
+
+#[proc_macro]
+pub fn select_indexed_default(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    select_internal(input, Flavor::Indexed)
+}
+
+#[proc_macro]
+pub fn select_loop_default(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    select_internal(input, Flavor::Loop)
+}
+
+#[proc_macro]
+pub fn try_select_default(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    select_internal(input, Flavor::Try)
+}
+
+#[proc_macro]
+pub fn poll_select_default(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    select_internal(input, Flavor::Poll)
+}
+
+#[proc_macro]
+pub fn join_default(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    join::join_internal(input, false)
 }
 
 #[proc_macro]
-pub fn select_biased(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    select_internal(input, true)
+pub fn try_join_default(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    join::join_internal(input, true)
 }
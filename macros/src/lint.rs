@@ -0,0 +1,160 @@
+//! Warnings about provably dead clauses of `select!`.
+//!
+//! There is no stable api for proc macros to emit warnings, so warnings are emitted as uses of
+//! deprecated constants located at offending tokens. They could be silenced by
+//! `#[allow(deprecated)]`.
+//!
+//! Clauses diverging by `unreachable!()` and alike are dead intentionally, so they are not warned.
+//! Duplicated literals in a pattern are left to `unreachable_patterns` of rustc.
+
+use proc_macro2::{Ident, TokenStream};
+use quote::{quote, ToTokens};
+use syn::spanned::Spanned;
+use syn::{Expr, ExprLit, Lit, Pat, Stmt};
+
+use crate::{Branch, Select};
+
+fn warning(tokens: &dyn ToTokens, note: &str) -> TokenStream {
+    let note = format!("`select!`: {note}");
+    let name = Ident::new("SELECT_WARNING", tokens.span());
+    quote! {
+        {
+            #[deprecated(note = #note)]
+            const #name: () = ();
+            let () = #name;
+        }
+    }
+}
+
+fn diverges(expr: &Expr) -> bool {
+    const DIVERGING_MACROS: [&str; 4] = ["unreachable", "unimplemented", "todo", "panic"];
+    let mac = match expr {
+        Expr::Macro(expr) => &expr.mac,
+        Expr::Block(expr) => match expr.block.stmts.as_slice() {
+            [Stmt::Expr(expr, _)] => return diverges(expr),
+            [Stmt::Macro(stmt)] => &stmt.mac,
+            _ => return false,
+        },
+        _ => return false,
+    };
+    mac.path.segments.last().is_some_and(|segment| DIVERGING_MACROS.iter().any(|name| segment.ident == name))
+}
+
+fn is_false(expr: &Expr) -> bool {
+    matches!(expr, Expr::Lit(ExprLit { lit: Lit::Bool(lit), .. }) if !lit.value)
+}
+
+/// Whether `expr` calls `core::future::{name}`, `std::future::{name}` or `futures::future::{name}`.
+///
+/// Calls of bare `{name}` are not matched, as they could be functions of users.
+fn calls(expr: &Expr, name: &str, args: usize) -> bool {
+    const CRATES: [&str; 3] = ["core", "std", "futures"];
+    let Expr::Call(call) = expr else {
+        return false;
+    };
+    let Expr::Path(path) = call.func.as_ref() else {
+        return false;
+    };
+    let segments: Vec<_> = path.path.segments.iter().map(|segment| &segment.ident).collect();
+    path.qself.is_none()
+        && call.args.len() == args
+        && matches!(segments.as_slice(), [krate, module, function]
+            if CRATES.iter().any(|expected| *krate == expected) && *module == "future" && *function == name)
+}
+
+// Capitalized identifiers are probably unit structs or variants, say `None`.
+fn irrefutable(pat: &Pat) -> bool {
+    match pat {
+        Pat::Ident(ident) => {
+            !ident.ident.to_string().starts_with(char::is_uppercase)
+                && ident.subpat.as_ref().is_none_or(|(_, pat)| irrefutable(pat))
+        },
+        Pat::Wild(_) | Pat::Rest(_) => true,
+        Pat::Paren(paren) => irrefutable(&paren.pat),
+        Pat::Reference(reference) => irrefutable(&reference.pat),
+        Pat::Tuple(tuple) => tuple.elems.iter().all(irrefutable),
+        Pat::Type(type_pat) => irrefutable(&type_pat.pat),
+        _ => false,
+    }
+}
+
+/// Literal alternatives of top level pattern.
+fn literals(pat: &Pat) -> Vec<&Pat> {
+    match pat {
+        Pat::Lit(_) => vec![pat],
+        Pat::Paren(paren) => literals(&paren.pat),
+        Pat::Or(or) => or.cases.iter().flat_map(literals).collect(),
+        _ => vec![],
+    }
+}
+
+fn same(a: &Pat, b: &Pat) -> bool {
+    a.to_token_stream().to_string() == b.to_token_stream().to_string()
+}
+
+impl Branch {
+    fn always_ready(&self) -> bool {
+        self.condition.is_none()
+            && self.stream.is_none()
+            && self.guard.is_none()
+            && irrefutable(&self.bind)
+            && calls(&self.future, "ready", 1)
+    }
+
+    // Branch could be disabled only by condition, refutable pattern, guard or end of stream.
+    fn never_disabled(&self) -> bool {
+        self.condition.is_none() && self.stream.is_none() && self.guard.is_none() && irrefutable(&self.bind)
+    }
+}
+
+pub(crate) fn lint(select: &Select, looping: bool) -> TokenStream {
+    let mut warnings = Vec::new();
+    for (i, branch) in select.branches.iter().enumerate() {
+        let diverging = diverges(&branch.clause.expr);
+        match branch.condition.as_ref() {
+            Some(condition) if !diverging && is_false(&condition.expr) => {
+                warnings.push(warning(condition, &format!("branch {i} is never enabled as its condition is `false`")))
+            },
+            _ if !diverging && branch.stream.is_none() && calls(&branch.future, "pending", 0) => {
+                warnings.push(warning(
+                    &branch.future,
+                    &format!("clause of branch {i} never executes as its future is always pending"),
+                ))
+            },
+            _ => {},
+        }
+        // Outputs failing guard fall to `else`.
+        let Some((pat, _)) = branch.else_clause.as_ref().and_then(|else_clause| else_clause.arm.as_ref()) else {
+            continue;
+        };
+        if branch.guard.is_some() {
+            continue;
+        }
+        let matched = literals(&branch.bind);
+        for literal in literals(pat) {
+            if matched.iter().any(|matched| same(matched, literal)) {
+                warnings.push(warning(
+                    literal,
+                    &format!(
+                        "`else` of branch {i} never matches literal `{}` as branch matches it",
+                        literal.to_token_stream()
+                    ),
+                ));
+            }
+        }
+    }
+    if looping {
+        return quote! { #(#warnings)* };
+    }
+    if let Some((default, clause)) = select.default_clause.as_ref() {
+        if let (false, Some(i)) = (diverges(&clause.expr), select.branches.iter().position(Branch::always_ready)) {
+            warnings.push(warning(default, &format!("`default` never fires as branch {i} is always ready")));
+        }
+    }
+    if let Some((complete, clause)) = select.complete_clause.as_ref() {
+        if !diverges(&clause.expr) && select.branches.iter().all(Branch::never_disabled) {
+            warnings.push(warning(complete, "`complete` never fires as no branch could be disabled"));
+        }
+    }
+    quote! { #(#warnings)* }
+}
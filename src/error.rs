@@ -0,0 +1,23 @@
+use core::fmt::{self, Display, Formatter};
+
+/// Error of `select!` with `nopanic;` when no branch is selected and there is no `default` nor
+/// `complete`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SelectError {
+    /// All branches are disabled by conditions, so no futures are polled.
+    Disabled,
+    /// All enabled branches are completed without selection, say, by mismatched patterns, failing
+    /// guards or ended streams.
+    Completed,
+}
+
+impl Display for SelectError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SelectError::Disabled => f.write_str("all branches are disabled"),
+            SelectError::Completed => f.write_str("all branches are completed without selection"),
+        }
+    }
+}
+
+impl core::error::Error for SelectError {}
@@ -0,0 +1,167 @@
+//! Start branch policies for unbiased selection.
+//!
+//! Unbiased selects start polling from a pseudo-random branch by default, or from a per-callsite
+//! branch next to the last selected one with `fair = round_robin;`, or from a branch generated from `seed = expr;`.
+//! Selects with weighted branches poll branches in weighted random order.
+
+#[cfg(not(feature = "std"))]
+use core::sync::atomic::AtomicU32;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+// Must not be zero, otherwise xorshift gets stuck.
+const INITIAL_STATE: u32 = 0x9E37_79B9;
+
+fn xorshift32(mut x: u32) -> u32 {
+    x ^= x << 13;
+    x ^= x >> 17;
+    x ^= x << 5;
+    x
+}
+
+fn state_of(seed: u64) -> u32 {
+    match (seed ^ (seed >> 32)) as u32 {
+        0 => INITIAL_STATE,
+        state => state,
+    }
+}
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static STATE: core::cell::Cell<u32> = core::cell::Cell::new(initial_state());
+}
+
+#[cfg(feature = "std")]
+fn initial_state() -> u32 {
+    use std::hash::{BuildHasher, Hasher};
+
+    // `RandomState` is randomly seeded per thread.
+    state_of(std::collections::hash_map::RandomState::new().build_hasher().finish())
+}
+
+/// Seeds pseudo-random start branches of unbiased selects in current thread.
+///
+/// Selects with `biased;`, `fair = round_robin;` or `seed = expr;` are not affected. This is mainly
+/// for reproducing polling orders in tests, which requires single threaded runtime.
+#[cfg(feature = "std")]
+pub fn set_seed(seed: u64) {
+    STATE.with(|state| state.set(state_of(seed)));
+}
+
+#[cfg(feature = "std")]
+fn next() -> u32 {
+    STATE.with(|state| {
+        let next = xorshift32(state.get());
+        state.set(next);
+        next
+    })
+}
+
+// There is no thread local in `no_std`, so all threads share one state. Racing updates could
+// produce duplicated numbers, which is harmless for start branches.
+#[cfg(not(feature = "std"))]
+static STATE: AtomicU32 = AtomicU32::new(INITIAL_STATE);
+
+#[cfg(not(feature = "std"))]
+fn next() -> u32 {
+    let next = xorshift32(STATE.load(Ordering::Relaxed));
+    STATE.store(next, Ordering::Relaxed);
+    next
+}
+
+/// Returns a pseudo-random start branch in `0..n`.
+pub fn random(n: usize) -> usize {
+    next() as usize % n
+}
+
+/// Returns a pseudo-random number.
+pub fn random_u32() -> u32 {
+    next()
+}
+
+/// Fills `order` with a random permutation of branches in which heavier branches tend to come first.
+///
+/// Branches are drawn one by one with probabilities proportional to their weights among remaining
+/// ones, so among any set of ready branches, each is polled first with probability proportional to
+/// its weight.
+pub fn weighted_order(weights: &[u32], order: &mut [usize], mut random: impl FnMut() -> u32) {
+    for (i, branch) in order.iter_mut().enumerate() {
+        *branch = i;
+    }
+    let mut total: u64 = weights.iter().map(|weight| u64::from(*weight)).sum();
+    for i in 0..order.len() {
+        let mut point = u64::from(random()) % total;
+        let mut chosen = order.len() - 1;
+        for (j, branch) in order.iter().enumerate().skip(i) {
+            let weight = u64::from(weights[*branch]);
+            if point < weight {
+                chosen = j;
+                break;
+            }
+            point -= weight;
+        }
+        order.swap(i, chosen);
+        total -= u64::from(weights[order[i]]);
+        if total == 0 {
+            break;
+        }
+    }
+}
+
+/// Start branch generator of a select with `seed = expr;`.
+pub struct Rng(u32);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(state_of(seed))
+    }
+
+    /// Returns next start branch in `0..n`.
+    pub fn next(&mut self, n: usize) -> usize {
+        self.next_u32() as usize % n
+    }
+
+    /// Returns next pseudo-random number.
+    pub fn next_u32(&mut self) -> u32 {
+        self.0 = xorshift32(self.0);
+        self.0
+    }
+}
+
+/// Polling order of library selects.
+pub(crate) enum Order {
+    Biased,
+    Random,
+    Seeded(Rng),
+}
+
+impl Order {
+    /// Returns start branch in `0..n` for next round of polling.
+    pub fn start(&mut self, n: usize) -> usize {
+        match self {
+            Order::Biased => 0,
+            Order::Random => random(n),
+            Order::Seeded(rng) => rng.next(n),
+        }
+    }
+}
+
+/// Start branch of a select callsite for `fair = round_robin;`, which is next to the last selected
+/// branch from that callsite.
+pub struct RoundRobin(AtomicUsize);
+
+impl RoundRobin {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        Self(AtomicUsize::new(0))
+    }
+
+    /// Returns start branch in `0..n`.
+    pub fn start(&self, n: usize) -> usize {
+        self.0.load(Ordering::Relaxed) % n
+    }
+
+    /// Records selected branch `index`, so next round starts from its next branch.
+    pub fn select(&self, index: usize) {
+        self.0.store(index + 1, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,32 @@
+/// Identity of the clause executed by [select_indexed!](crate::select_indexed!).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SelectedBranch {
+    /// Branch at `index` in declaration order, `label` is its label without leading `'`.
+    Future { index: usize, label: Option<&'static str> },
+    /// `default` clause, including the case it acts as `complete`.
+    Default,
+    /// `complete` clause.
+    Complete,
+    /// `timeout` clause.
+    Timeout,
+}
+
+impl SelectedBranch {
+    /// Returns index of the selected branch if it is not a clause without future.
+    pub fn index(&self) -> Option<usize> {
+        match self {
+            SelectedBranch::Future { index, .. } => Some(*index),
+            _ => None,
+        }
+    }
+
+    /// Returns label of the selected branch, or names of clauses without future.
+    pub fn label(&self) -> Option<&'static str> {
+        match self {
+            SelectedBranch::Future { label, .. } => *label,
+            SelectedBranch::Default => Some("default"),
+            SelectedBranch::Complete => Some("complete"),
+            SelectedBranch::Timeout => Some("timeout"),
+        }
+    }
+}
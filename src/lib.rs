@@ -1,24 +1,233 @@
 #![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+mod error;
+mod fairness;
+mod indexed;
+mod recording;
+mod report;
+mod select;
+mod select_all;
+mod stream;
+mod timer;
+mod waker;
+
+pub use error::SelectError;
+#[cfg(feature = "std")]
+pub use fairness::set_seed;
+pub use indexed::SelectedBranch;
+#[cfg(feature = "recording")]
+pub use recording::Recorder;
+pub use report::{BranchEnd, CompleteReport};
+pub use select::{Branches, IntoBranches, Select, Selected};
+pub use select_all::{select_array, SelectArray};
+#[cfg(feature = "alloc")]
+pub use select_all::{select_iter, select_vec, SelectVec};
+pub use stream::Stream;
+#[cfg(feature = "async-io")]
+pub use timer::AsyncIoTimer;
+#[cfg(any(feature = "tokio", feature = "async-io"))]
+pub use timer::DefaultSleep;
+#[cfg(feature = "smol")]
+pub use timer::SmolTimer;
+#[cfg(feature = "tokio")]
+pub use timer::TokioTimer;
+pub use timer::{DefaultTimer, Timer};
+
 /// # Select multiplex asynchronous futures simultaneously
 ///
-/// `select!` supports three different clauses:
+/// `select!` supports six different clauses:
 ///
-/// * pattern = future [, if condition] => code,
+/// * pattern = future [, if condition] [, weight N] [if guard] => code,
+/// * pattern = repeat future [, if condition] [, weight N] [if guard] => code,
+/// * pattern = \[repeat\] stream in stream [, if condition] [, weight N] [if guard] => code,
 /// * default => code,
-/// * complete => code,
+/// * complete [(report)] => code,
+/// * timeout(duration) => code,
+///
+/// Branches could be followed by `else [continue] [pattern => code]` to handle pattern mismatches.
+/// Branches could be labeled as `'label: pattern = future` for [select_indexed!].
+///
+/// Branches and clauses could be prefixed with outer attributes. `#[cfg]`s are resolved before
+/// expansion, so branches configured out are excluded entirely. Other attributes are put on the
+/// generated match arms.
 ///
 /// ## Evaluation order
 /// * All conditions and futures are evaluated before selection.
 /// * Future expression is not evaluated if corresponding condition evaluated to false.
 /// * Whenever a branch is ready, its clause is executed. And the whole select returns.
-/// * Fail to match a refutable pattern will disable that branch.
+/// * Guard is evaluated with bindings of matched pattern. Failing guard counts as pattern mismatch.
+/// * Fail to match a refutable pattern will disable that branch. `else pattern => code` executes
+///   `code` with the mismatched output, and the select continues. `else continue` rearms the branch
+///   as `repeat` does.
 /// * `default` clause is executed if no futures are ready. That is non blocking mode.
 /// * If all branches are disabled by conditions or refutable pattern match, it resort to
 ///   `complete` or `default` in case of no `complete`.
+/// * Clause of `repeat` branch does not end the select. Instead, the branch is rearmed by evaluating
+///   its condition and future again, and the select continues with futures of other branches.
+///   Clause of `repeat` branch must evaluate to `()`. It is executed inside a loop of the select, so
+///   unlabeled `break` and `continue` are rejected, use labeled ones to target enclosing loops.
+/// * `repeat` branch disabled by condition or refutable pattern match is not rearmed.
+///
+/// * Stream branch matches pattern against items from [Stream]. End of stream disables that branch.
+///   Stream is evaluated once and kept until end, even in `repeat` branch, so it could borrow
+///   mutably.
+/// * `timeout` clause acts as the last branch with a sleep future from [Timer]. So it counts as an
+///   enabled branch for `complete`.
 ///
 /// ## Panics
-/// * Panic when all futures are disabled or completed and there is no `default` or `complete`.
+/// * Panic when all futures are disabled or completed and there is no `default` or `complete`,
+///   unless `nopanic;` is specified.
+///   The panic message lists source, line and end of each branch, say, condition is false or
+///   pattern mismatch.
+///
+/// ## Repeat
+/// `repeat` branches drive futures repeatedly without rebuilding the select.
+/// ```
+/// use core::future::{pending, ready};
+/// use async_select::select;
+///
+/// async fn sum() {
+///     let mut i = 0;
+///     let mut sum = 0;
+///     let r = select! {
+///         _ = pending::<()>() => unreachable!(),
+///         v = repeat ready(i), if i < 5 => {
+///             sum += v;
+///             i += 1;
+///         },
+///         complete => sum,
+///     };
+///     assert_eq!(r, 10);
+/// }
+/// ```
+/// Future of `repeat` branch is dropped before the branch is rearmed, so the `repeat` expression
+/// could borrow mutably, say, `v = repeat rx.recv()`. Futures of other branches are kept alive
+/// meanwhile, so the `repeat` expression and clause must not borrow what they borrow mutably.
+///
+/// ## Stream
+/// Stream branch selects items from [Stream] without depending on `futures`. Combining with `repeat`,
+/// it drives the stream until other branches are selected.
+/// ```
+/// use core::pin::Pin;
+/// use core::task::{Context, Poll};
+/// use async_select::{select, Stream};
+///
+/// struct Counter(u32);
+///
+/// impl Stream for Counter {
+///     type Item = u32;
+///
+///     fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<u32>> {
+///         self.0 += 1;
+///         Poll::Ready(Some(self.0).filter(|n| *n <= 3))
+///     }
+/// }
+///
+/// async fn sum() {
+///     let mut sum = 0;
+///     let r = select! {
+///         n = repeat stream in Counter(0) => sum += n,
+///         complete => sum,
+///     };
+///     assert_eq!(r, 6);
+/// }
+/// ```
+///
+/// ## Complete report
+/// Use `complete(report) => code` to bind a [CompleteReport] to `report`, which tells how each
+/// branch ended, say, disabled by its condition, mismatched its pattern or ended its stream.
+/// ```
+/// use core::future::ready;
+/// use async_select::{select, BranchEnd};
+///
+/// async fn report() {
+///     let enabled = false;
+///     let report = select! {
+///         _ = ready(5), if enabled => unreachable!(),
+///         Some(_) = ready(None::<i32>) => unreachable!(),
+///         complete(report) => report,
+///     };
+///     assert_eq!(report.get(0), Some(BranchEnd::Disabled));
+///     assert_eq!(report.get(1), Some(BranchEnd::Mismatched));
+/// }
+/// ```
+///
+/// ## Guard
+/// `if guard` after future filters outputs as match guard does, while `, if condition` decides
+/// whether to evaluate the future at all.
+/// ```
+/// use core::future::ready;
+/// use async_select::select;
+///
+/// async fn positive() {
+///     let r = select! {
+///         Some(n) = ready(Some(-1)) if n > 0 => n,
+///         complete => 0,
+///     };
+///     assert_eq!(r, 0);
+/// }
+/// ```
+///
+/// ## Else
+/// `else` handles outputs failing branch pattern instead of dropping them silently. Its pattern must
+/// be irrefutable and its clause must evaluate to `()`. It is executed inside a loop of the select, so unlabeled `break` and `continue`
+/// are rejected, use labeled ones to target enclosing loops.
+/// ```
+/// use std::collections::VecDeque;
+/// use core::future::{pending, ready};
+/// use async_select::select;
+///
+/// async fn skip() {
+///     let mut messages = VecDeque::from([None, Some(1)]);
+///     let mut skipped = 0;
+///     let r = select! {
+///         Some(v) = ready(messages.pop_front().unwrap()) => v,
+///         else continue _ => skipped += 1,
+///         _ = pending::<()>() => unreachable!(),
+///     };
+///     assert_eq!(r, 1);
+///     assert_eq!(skipped, 1);
+/// }
+/// ```
+///
+/// ## Lints
+/// `select!` warns about clauses it could prove dead:
+/// * clause of `core::future::pending()` branch or branch with `, if false`.
+/// * `default` with an unconditional `core::future::ready(...)` branch of irrefutable pattern.
+/// * `complete` without any branch that could be disabled.
+/// * literal in `else` pattern that branch pattern matches.
+///
+/// Futures are recognized only by paths `core::future`, `std::future` or `futures::future`, as bare
+/// `pending()` and `ready(...)` could be functions of users. Duplicated literals in a pattern are
+/// left to `unreachable_patterns` of rustc.
+///
+/// Clauses diverging by `unreachable!()`, `unimplemented!()`, `todo!()` or `panic!()` are deemed
+/// intended. Warnings are reported as uses of deprecated items, so `#[allow(deprecated)]` silences
+/// them.
+///
+/// ## Attributes
+/// ```
+/// use core::future::{pending, ready};
+/// use async_select::select;
+///
+/// async fn configured() {
+///     let r = select! {
+///         #[cfg(any())]
+///         v = ready(5) => v,
+///         _ = pending::<()>() => unreachable!(),
+///         #[cfg(all())]
+///         default => 6,
+///         #[cfg(not(all()))]
+///         default => 7,
+///     };
+///     assert_eq!(r, 6);
+/// }
+/// ```
 ///
 /// ## Comparing with `tokio::select!`
 /// * Future expression is only evaluated if condition meets.
@@ -41,10 +250,21 @@
 ///   and `ready(())`.`complete` is same as `else` in `tokio::select!`.
 /// * `async_select::select!` depends only on `proc_macro` macros and hence the generated code is
 ///   `no_std` compatible.
+/// * Patterns are checked against outputs but not references to them, so reference patterns, say
+///   `&[a, ..] = ready(&[1, 2][..])`, match as they do in `match`.
 ///
 /// ## Polling order
 /// By default, the polling order of each branch is indeterminate. Use `biased;` to poll
 /// sequentially if desired.
+///
+/// Unbiased select starts polling from a pseudo-random branch, so no ready branch is starved.
+/// The generator is xorshift seeded per thread with feature `std`, or shared by all threads
+/// otherwise. Use `fair = round_robin;` to start from next branch of last selection from the same
+/// callsite instead, or `fair = random;` to be explicit.
+///
+/// Use `seed = expr;` to generate start branches from a `u64` seed, or `set_seed` with feature `std`
+/// to seed the generator of current thread, so selections are reproducible. With feature
+/// `recording`, `Recorder` records selected branches for replaying.
 /// ```
 /// use async_select::select;
 /// use core::future::{pending, ready};
@@ -63,8 +283,132 @@
 /// }
 /// ```
 ///
+/// ## Timeout
+/// `timeout(duration) => code` selects `code` if no branches are selected in `duration`. It sleeps
+/// with [DefaultTimer] from cargo features `tokio`, `async-io` or `smol`, or the [Timer] specified
+/// by `timer = Type;`, so the select is free from timers of runtimes. [DefaultTimer] picks tokio in
+/// context of tokio runtime and async-io otherwise, so enabling more features elsewhere never breaks
+/// it. In `select_loop!`, the timeout covers the whole loop as other futures are kept.
+/// ```
+/// use core::future::{pending, Ready, ready};
+/// use core::time::Duration;
+/// use async_select::{select, Timer};
+///
+/// struct Immediate;
+///
+/// impl Timer for Immediate {
+///     type Sleep = Ready<()>;
+///
+///     fn sleep(_duration: Duration) -> Ready<()> {
+///         ready(())
+///     }
+/// }
+///
+/// async fn timeout() {
+///     let r = select! {
+///         timer = Immediate;
+///         _ = pending::<()>() => unreachable!(),
+///         timeout(Duration::from_secs(5)) => 5,
+///     };
+///     assert_eq!(r, 5);
+/// }
+/// ```
+///
+/// ## Weights
+/// Use `, weight N` to prioritize branches without starving others as `biased;` does. Branches are
+/// polled in weighted random order, so among ready branches, each is selected with probability
+/// proportional to its weight. Weights must be positive integer literals and default to `1`.
+/// ```
+/// use async_select::select;
+/// use core::future::ready;
+///
+/// async fn prioritize() {
+///     let r = select! {
+///         v = ready("control"), weight 4 => v,
+///         v = ready("bulk") => v,
+///     };
+///     assert!(r == "control" || r == "bulk");
+/// }
+/// ```
+///
+/// ## Keep unselected futures
+/// By default, futures of unselected branches are dropped after selection. Use `keep pattern;` to
+/// bind futures of all branches as a tuple of `Option`s to `pattern` in clauses, so unselected futures
+/// could be resumed in later selects without losing partial works. Futures of selected, completed
+/// or disabled branches are `None`. Kept futures are moved after polling, so they must be [Unpin].
+/// ```
+/// use async_select::select;
+/// use core::future::{pending, ready};
+///
+/// async fn resume() {
+///     let (v, future) = select! {
+///         keep (_, future);
+///         v = ready(5) => (v, future),
+///         _ = pending::<i32>() => unreachable!(),
+///     };
+///     assert_eq!(v, 5);
+///     let r = select! {
+///         v = future.unwrap(), if future.is_some() => v,
+///         default => 6,
+///     };
+///     assert_eq!(r, 6);
+/// }
+/// ```
+///
+/// ## Compact
+/// Use `compact;` to expand to [Select] instead of generating polling code for each select. It saves
+/// code size at the cost of `repeat`, `stream`, `keep`, `weight`, `else` and `fair = round_robin`,
+/// and supports at most 64 branches.
+/// ```
+/// use async_select::select;
+/// use core::future::{pending, ready};
+///
+/// async fn compact() {
+///     let r = select! {
+///         compact;
+///         _ = pending::<()>() => unreachable!(),
+///         Some(v) = ready(None::<i32>) => v,
+///         v = ready(6) => v,
+///     };
+///     assert_eq!(r, 6);
+/// }
+/// ```
+///
+/// ## No panic
+/// Use `nopanic;` to evaluate to `Ok` of clause value, or `Err` of [SelectError] instead of panic
+/// when all branches are disabled or completed and there is no `default` nor `complete`. See also
+/// [select_opt!].
+/// ```
+/// use async_select::{select, SelectError};
+/// use core::future::ready;
+///
+/// async fn nopanic() {
+///     let enabled = false;
+///     let r = select! {
+///         nopanic;
+///         v = ready(5), if enabled => v,
+///     };
+///     assert_eq!(r, Err(SelectError::Disabled));
+///     let r = select! {
+///         nopanic;
+///         v = ready(5), if enabled => v,
+///         Some(v) = ready(None) => v,
+///     };
+///     assert_eq!(r, Err(SelectError::Completed));
+/// }
+/// ```
+///
 /// ## Efficiency
-/// `select!` blindly `Future:poll` all enabled futures without checking for waking branch.
+/// `select!` polls each branch with a waker wrapping the outer one, so it polls only branches that are
+/// woken since their last polls. Wakeups are recorded in a bitset. A branch waker lives only during
+/// its poll. With feature `cached-waker`, cloning it returns a waker cached for the branch, which
+/// tells the branch on wakeup, so futures storing wakers, say channels, are skipped too. The cached
+/// waker is allocated on first clone and reused as long as the outer waker stays the same. No other
+/// feature enables `cached-waker`. Without it, the generated code is allocation free, but cloning a
+/// branch waker results in a clone of the outer waker, which can't tell the waking branch, so only
+/// futures not cloning their wakers are skipped. Branches cloned their wakers are polled on every
+/// wakeup until rearmed. On targets without atomic read-modify-write, say `thumbv6m-none-eabi`,
+/// wakeups are not tracked and all branches are polled on every wakeup.
 ///
 /// ## Examples
 /// ```rust
@@ -82,17 +426,316 @@
 /// ```
 #[macro_export]
 macro_rules! select {
-    (biased; $($token:tt)*) => {
-        $crate::select_biased! { $($token)* }
+    ($($token:tt)*) => {
+        $crate::select_default! { $crate; $($token)* }
     };
+}
+
+/// # Select to `Option` instead of panic
+///
+/// `select_opt!` is [select!] with `nopanic;`, but evaluates to `Some` of clause value or `None`
+/// when all branches are disabled or completed and there is no `default` nor `complete`.
+///
+/// ## Examples
+/// ```rust
+/// use async_select::select_opt;
+/// use core::future::ready;
+///
+/// async fn select_opt() {
+///     let r = select_opt! {
+///         Some(v) = ready(Some(5)) => v,
+///     };
+///     assert_eq!(r, Some(5));
+///     let r = select_opt! {
+///         Some(v) = ready(None::<i32>) => v,
+///     };
+///     assert_eq!(r, None);
+/// }
+/// ```
+#[macro_export]
+macro_rules! select_opt {
+    ($($token:tt)*) => {
+        ::core::result::Result::ok($crate::select_default! { $crate; nopanic; $($token)* })
+    };
+}
+
+/// # Loop over [select!] with futures kept across iterations
+///
+/// `select_loop!` supports all clauses of [select!].
+///
+/// ## Evaluation order
+/// * All conditions and futures are evaluated in order once before the loop, so futures could
+///   borrow mutably, say, `v = rx.recv() => ...`.
+/// * Whenever a branch is ready, its clause is executed. And then next iteration starts.
+/// * Futures of unselected branches are kept for following iterations.
+/// * Ready branches are disabled except `repeat` branches, which are rearmed by evaluating their
+///   conditions and futures again at the start of next iteration.
+/// * All clauses are bodies of the loop, so they must evaluate to `()` or diverge.
+/// * `break value` in clauses exits the loop with value. `continue` starts next iteration.
+///
+/// ## Panics
+/// * Panic when all futures are disabled or completed and there is no `default` or `complete`.
+///
+/// ## Examples
+/// ```rust
+/// use async_select::select_loop;
+/// use core::future::{pending, ready};
+///
+/// async fn count() {
+///     let mut i = 0;
+///     let r = select_loop! {
+///         _ = pending::<()>() => unreachable!(),
+///         v = repeat ready(i) => {
+///             if v == 5 {
+///                 break v;
+///             }
+///             i += 1;
+///         },
+///     };
+///     assert_eq!(r, 5);
+/// }
+/// ```
+#[macro_export]
+macro_rules! select_loop {
+    ($($token:tt)*) => {
+        $crate::select_loop_default! { $crate; $($token)* }
+    };
+}
+
+/// # Select asynchronous fallible futures
+///
+/// `try_select!` is [select!] for futures resolving to `Result<T, E>` with same `E`, it supports all
+/// clauses of [select!] plus:
+///
+/// * error(pattern) => code,
+///
+/// ## Evaluation order
+/// * Patterns of branches match against `Ok` values. Refutable patterns disable branches only if
+///   they fail to match `Ok` values.
+/// * Without `error` clause, `try_select!` evaluates to `Ok` of clause value from selected branch,
+///   or the `Err` from selected branch. So `try_select! { ... }?` propagates errors as `?`.
+/// * With `error` clause, errors from selected branches are bound to its pattern and `try_select!`
+///   evaluates to clause value as `select!` does.
+/// * Errors from `repeat` branches end the select.
+///
+/// ## Examples
+/// ```rust
+/// use async_select::try_select;
+/// use core::future::{pending, ready};
+///
+/// async fn propagate() -> Result<i32, &'static str> {
+///     let r = try_select! {
+///         v = ready(Ok(5)) => v + 1,
+///         _ = pending::<Result<i32, &str>>() => unreachable!(),
+///     }?;
+///     assert_eq!(r, 6);
+///     try_select! {
+///         v = ready(Err("failure")) => v,
+///     }
+/// }
+///
+/// async fn handle() {
+///     let r = try_select! {
+///         v = ready(Err::<i32, i32>(5)) => v,
+///         error(e) => e * 2,
+///     };
+///     assert_eq!(r, 10);
+/// }
+/// ```
+#[macro_export]
+macro_rules! try_select {
     ($($token:tt)*) => {
-        $crate::select_default! { $($token)* }
+        $crate::try_select_default! { $crate; $($token)* }
+    };
+}
+
+/// # Select with identity of the executed clause
+///
+/// `select_indexed!` supports all clauses of [select!], and evaluates to a tuple of
+/// [SelectedBranch] and clause value. Branches could be labeled as `'label: pattern = future`,
+/// labels are reported without the leading `'`, so per branch metrics and traces need no string
+/// literals in clauses.
+///
+/// ## Examples
+/// ```rust
+/// use async_select::{select_indexed, SelectedBranch};
+/// use core::future::{pending, ready};
+///
+/// async fn trace() {
+///     let (branch, v) = select_indexed! {
+///         'idle: _ = pending::<()>() => unreachable!(),
+///         'read: v = ready(5) => v,
+///     };
+///     assert_eq!(branch, SelectedBranch::Future { index: 1, label: Some("read") });
+///     assert_eq!(branch.label(), Some("read"));
+///     assert_eq!(v, 5);
+///
+///     let (branch, v) = select_indexed! {
+///         _ = pending::<()>() => unreachable!(),
+///         default => 6,
+///     };
+///     assert_eq!(branch, SelectedBranch::Default);
+///     assert_eq!(v, 6);
+/// }
+/// ```
+#[macro_export]
+macro_rules! select_indexed {
+    ($($token:tt)*) => {
+        $crate::select_indexed_default! { $crate; $($token)* }
+    };
+}
+
+/// # Select in hand-written `poll` functions
+///
+/// `poll_select!(cx, ...)` polls branches of [select!] once with `cx: &mut Context<'_>` and
+/// evaluates to `Poll` of clause value, so it could be used in [Future::poll](core::future::Future::poll)
+/// or [Stream::poll_next] of state machines.
+///
+/// ## Evaluation order
+/// * All conditions and futures are evaluated in every poll, so futures should be references to
+///   stored futures, say `Pin<&mut F>` or `&mut F` for `F: Unpin`.
+/// * Whenever a branch is ready, its clause is executed and `poll_select!` evaluates to
+///   `Poll::Ready`. Clauses are not `async`, `return` in clauses returns from the `poll` function.
+/// * Otherwise, it evaluates to `Poll::Ready` of `default` clause if any, or `Poll::Pending`.
+/// * `complete` is executed if all branches are disabled in this poll. Completed futures are not
+///   tracked across polls, so they should be disabled by conditions.
+/// * Mismatched outputs are handed to `else` clauses, after which conditions and futures are
+///   evaluated again, so `else` clauses should disable their branches. Branches of futures without
+///   `else` must have irrefutable patterns and no guards, as mismatches of them would be lost. Streams
+///   are not completed by mismatches, so they need no `else`.
+///
+/// `repeat`, `else continue`, `seed`, `timeout`, `keep` and `compact` are not supported.
+///
+/// ## Examples
+/// ```rust
+/// use core::future::{pending, ready, Future, Pending, Ready};
+/// use core::pin::Pin;
+/// use core::task::{Context, Poll};
+/// use async_select::poll_select;
+///
+/// struct Race {
+///     fast: Option<Ready<i32>>,
+///     slow: Pending<i32>,
+/// }
+///
+/// impl Future for Race {
+///     type Output = i32;
+///
+///     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<i32> {
+///         let this = &mut *self;
+///         poll_select!(cx,
+///             v = this.fast.as_mut().unwrap(), if this.fast.is_some() => {
+///                 this.fast = None;
+///                 v
+///             },
+///             v = &mut this.slow => v,
+///         )
+///     }
+/// }
+///
+/// async fn race() {
+///     let r = Race { fast: Some(ready(5)), slow: pending() }.await;
+///     assert_eq!(r, 5);
+/// }
+/// ```
+#[macro_export]
+macro_rules! poll_select {
+    ($cx:expr, $($token:tt)*) => {
+        $crate::poll_select_default! { $crate; $cx, $($token)* }
+    };
+}
+
+/// # Join asynchronous futures concurrently
+///
+/// `join!` shares branch syntax with [select!]:
+///
+/// * pattern = future [, if condition] => code,
+///
+/// ## Evaluation order
+/// * All conditions and futures are evaluated before joining.
+/// * Future expression is not evaluated if corresponding condition evaluated to false.
+/// * All futures are polled concurrently until they are all completed.
+/// * Clauses are executed in order after all futures completed. Patterns must be irrefutable.
+///
+/// It evaluates to a tuple of clause values in branch order. Values of branches with conditions are
+/// wrapped in `Option`, which are `None` if corresponding conditions evaluated to false.
+///
+/// ## Examples
+/// ```rust
+/// use async_select::join;
+/// use core::future::ready;
+///
+/// async fn join() {
+///     let enabled = false;
+///     let (a, b, c) = join! {
+///         v = ready(5) => v,
+///         v = ready(6) => v * 2,
+///         v = ready(7), if enabled => v,
+///     };
+///     assert_eq!((a, b, c), (5, 12, None));
+/// }
+/// ```
+#[macro_export]
+macro_rules! join {
+    ($($token:tt)*) => {
+        $crate::join_default! { $crate; $($token)* }
+    };
+}
+
+/// # Join asynchronous fallible futures concurrently
+///
+/// `try_join!` is [join!] for futures resolving to `Result<T, E>` with same `E`. Patterns bind to
+/// `Ok` values. It evaluates to `Ok` of joined tuple, or the first `Err` from futures, in which case
+/// other futures are dropped without completion.
+///
+/// ## Examples
+/// ```rust
+/// use async_select::try_join;
+/// use core::future::{pending, ready};
+///
+/// async fn try_join() {
+///     let r = try_join! {
+///         v = ready(Ok::<i32, &str>(5)) => v,
+///         v = ready(Ok(6)), if false => v,
+///     };
+///     assert_eq!(r, Ok((5, None)));
+///
+///     let r = try_join! {
+///         v = pending::<Result<i32, &str>>() => v,
+///         v = ready(Err("failure")) => v,
+///     };
+///     assert_eq!(r, Err("failure"));
+/// }
+/// ```
+#[macro_export]
+macro_rules! try_join {
+    ($($token:tt)*) => {
+        $crate::try_join_default! { $crate; $($token)* }
     };
 }
 
 // By importing them into this crate and using `$crate::select_xyz`, caller crates
 // are free from depending on `async-select-proc-macros` directly.
 #[doc(hidden)]
-pub use async_select_proc_macros::select_biased;
+pub mod __private {
+    pub use crate::fairness::{random, random_u32, weighted_order, Rng, RoundRobin};
+    pub use crate::recording::record;
+    pub use crate::report::{exhausted, BranchSource};
+    pub use crate::select::select_filtered;
+    pub use crate::waker::{wake_words, WakeSet, Wakes};
+}
+
+#[doc(hidden)]
+pub use async_select_proc_macros::join_default;
+#[doc(hidden)]
+pub use async_select_proc_macros::poll_select_default;
 #[doc(hidden)]
 pub use async_select_proc_macros::select_default;
+#[doc(hidden)]
+pub use async_select_proc_macros::select_indexed_default;
+#[doc(hidden)]
+pub use async_select_proc_macros::select_loop_default;
+#[doc(hidden)]
+pub use async_select_proc_macros::try_join_default;
+#[doc(hidden)]
+pub use async_select_proc_macros::try_select_default;
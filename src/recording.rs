@@ -0,0 +1,82 @@
+//! Test hook to observe selected branches.
+
+#[cfg(feature = "recording")]
+use std::cell::RefCell;
+#[cfg(feature = "recording")]
+use std::vec::Vec;
+
+#[cfg(feature = "recording")]
+std::thread_local! {
+    static SELECTIONS: RefCell<Option<Vec<usize>>> = const { RefCell::new(None) };
+}
+
+/// Records branch `index` as selected if there is an active `Recorder` in current thread.
+#[inline]
+pub fn record(index: usize) {
+    #[cfg(feature = "recording")]
+    SELECTIONS.with_borrow_mut(|selections| {
+        if let Some(selections) = selections {
+            selections.push(index);
+        }
+    });
+    #[cfg(not(feature = "recording"))]
+    let _ = index;
+}
+
+/// Recorder of branches selected in current thread, until it is dropped.
+///
+/// It records indices of selected branches, in declaration order, from `select!`, `select_loop!`,
+/// [Select](crate::Select), [select_array](crate::select_array) and `select_vec`. Together with
+/// `seed = expr;` or [set_seed](crate::set_seed), it helps to replay selections in tests. It requires
+/// single threaded runtime to observe selects from other tasks.
+///
+/// ```
+/// use core::future::ready;
+/// use async_select::{select, Recorder};
+///
+/// async fn record() {
+///     let recorder = Recorder::start();
+///     select! {
+///         biased;
+///         _ = ready(()) => {},
+///         _ = ready(()) => {},
+///     };
+///     assert_eq!(recorder.finish(), vec![0]);
+/// }
+/// ```
+#[cfg(feature = "recording")]
+pub struct Recorder {
+    _private: (),
+}
+
+#[cfg(feature = "recording")]
+impl Recorder {
+    /// Starts recording selections in current thread.
+    ///
+    /// ## Panics
+    /// * Panic if there is an active recorder in current thread.
+    pub fn start() -> Self {
+        SELECTIONS.with_borrow_mut(|selections| {
+            assert!(selections.is_none(), "Recorder: already recording in current thread");
+            *selections = Some(Vec::new());
+        });
+        Recorder { _private: () }
+    }
+
+    /// Returns selections recorded so far.
+    pub fn selections(&self) -> Vec<usize> {
+        SELECTIONS.with_borrow(|selections| selections.clone().unwrap_or_default())
+    }
+
+    /// Stops recording and returns all recorded selections.
+    pub fn finish(self) -> Vec<usize> {
+        self.selections()
+    }
+}
+
+#[cfg(feature = "recording")]
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        SELECTIONS.with_borrow_mut(|selections| *selections = None);
+    }
+}
@@ -0,0 +1,102 @@
+use core::fmt::{self, Debug, Display, Formatter};
+
+/// How a branch ended before `complete(report) => ...` of `select!` fires.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BranchEnd {
+    /// Disabled by its condition, so it is never polled since its last arming.
+    Disabled,
+    /// Completed with output failing its pattern or guard.
+    Mismatched,
+    /// Stream of the branch ended.
+    Ended,
+    /// Selected in earlier iterations of `select_loop!`.
+    Selected,
+}
+
+/// Report of why each branch ended for `complete(report) => ...` of `select!`.
+///
+/// Branches are indexed in declaration order, with `timeout` as the last one. It takes one byte
+/// per branch and no allocation.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CompleteReport<const N: usize> {
+    ends: [BranchEnd; N],
+}
+
+impl<const N: usize> CompleteReport<N> {
+    #[doc(hidden)]
+    pub const fn new() -> Self {
+        Self { ends: [BranchEnd::Disabled; N] }
+    }
+
+    #[doc(hidden)]
+    pub fn set(&mut self, index: usize, end: BranchEnd) {
+        self.ends[index] = end;
+    }
+
+    /// Number of branches.
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    /// Whether there is no branch.
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    /// Returns how branch `index` ended, or `None` if it is out of range.
+    pub fn get(&self, index: usize) -> Option<BranchEnd> {
+        self.ends.get(index).copied()
+    }
+
+    /// Iterates over ends of branches in declaration order.
+    pub fn iter(&self) -> impl Iterator<Item = BranchEnd> + '_ {
+        self.ends.iter().copied()
+    }
+}
+
+impl<const N: usize> Debug for CompleteReport<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.ends.iter()).finish()
+    }
+}
+
+/// Source of a branch to tell in panic message of exhausted `select!`.
+#[doc(hidden)]
+pub struct BranchSource {
+    pub future: &'static str,
+    pub condition: Option<&'static str>,
+    pub file: &'static str,
+    pub line: u32,
+}
+
+struct Exhaustion<'a> {
+    ends: &'a [BranchEnd],
+    sources: &'a [BranchSource],
+}
+
+impl Display for Exhaustion<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (i, (end, source)) in self.ends.iter().zip(self.sources.iter()).enumerate() {
+            write!(f, "\n    branch {i} at {}:{}: `{}`", source.file, source.line, source.future)?;
+            if let Some(condition) = source.condition {
+                write!(f, ", if `{condition}`")?;
+            }
+            let end = match end {
+                BranchEnd::Disabled => "condition is false",
+                BranchEnd::Mismatched => "pattern mismatch",
+                BranchEnd::Ended => "stream ended",
+                BranchEnd::Selected => "selected",
+            };
+            write!(f, ": {end}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Panics with how each branch ended as there is no `default` nor `complete`.
+#[doc(hidden)]
+#[track_caller]
+pub fn exhausted<const N: usize>(report: &CompleteReport<N>, sources: &[BranchSource; N]) -> ! {
+    let exhaustion = Exhaustion { ends: &report.ends, sources };
+    panic!("all branches are disabled or completed and there is no `default` nor `complete`:{exhaustion}")
+}
@@ -0,0 +1,383 @@
+use core::convert::Infallible;
+use core::future::{poll_fn, Future};
+use core::pin::{pin, Pin};
+use core::task::{Context, Poll};
+
+use crate::fairness::{Order, Rng};
+use crate::recording::record;
+use crate::waker::{wake_words, WakeSet, Wakes};
+
+macro_rules! define_selected {
+    ($(($t:ident $v:ident))*) => {
+        /// Output of [Select].
+        ///
+        /// Variant `_i` holds output of the `i`th future. Variants of absent futures are uninhabited.
+        #[allow(non_camel_case_types)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum Selected<$($t = Infallible),*> {
+            $(
+                #[allow(missing_docs)]
+                $v($t),
+            )*
+            /// No futures are ready in non blocking mode.
+            Default,
+            /// All futures are disabled or completed.
+            Complete,
+        }
+    };
+}
+
+define_selected! {
+    (T0 _0)
+    (T1 _1)
+    (T2 _2)
+    (T3 _3)
+    (T4 _4)
+    (T5 _5)
+    (T6 _6)
+    (T7 _7)
+    (T8 _8)
+    (T9 _9)
+    (T10 _10)
+    (T11 _11)
+    (T12 _12)
+    (T13 _13)
+    (T14 _14)
+    (T15 _15)
+    (T16 _16)
+    (T17 _17)
+    (T18 _18)
+    (T19 _19)
+    (T20 _20)
+    (T21 _21)
+    (T22 _22)
+    (T23 _23)
+    (T24 _24)
+    (T25 _25)
+    (T26 _26)
+    (T27 _27)
+    (T28 _28)
+    (T29 _29)
+    (T30 _30)
+    (T31 _31)
+    (T32 _32)
+    (T33 _33)
+    (T34 _34)
+    (T35 _35)
+    (T36 _36)
+    (T37 _37)
+    (T38 _38)
+    (T39 _39)
+    (T40 _40)
+    (T41 _41)
+    (T42 _42)
+    (T43 _43)
+    (T44 _44)
+    (T45 _45)
+    (T46 _46)
+    (T47 _47)
+    (T48 _48)
+    (T49 _49)
+    (T50 _50)
+    (T51 _51)
+    (T52 _52)
+    (T53 _53)
+    (T54 _54)
+    (T55 _55)
+    (T56 _56)
+    (T57 _57)
+    (T58 _58)
+    (T59 _59)
+    (T60 _60)
+    (T61 _61)
+    (T62 _62)
+    (T63 _63)
+}
+
+/// Tuple of optional futures to select.
+///
+/// It is implemented for tuples of `Option<F: Future>` up to 64 arity. `None` means disabled.
+pub trait Branches {
+    /// Output of selection.
+    type Output;
+
+    /// Number of branches.
+    const LEN: usize;
+
+    #[doc(hidden)]
+    const DEFAULT: Self::Output;
+
+    #[doc(hidden)]
+    const COMPLETE: Self::Output;
+
+    #[doc(hidden)]
+    type Wakes: Wakes;
+
+    #[doc(hidden)]
+    fn is_terminated(&self, index: usize) -> bool;
+
+    #[doc(hidden)]
+    fn poll_branch(self: Pin<&mut Self>, index: usize, cx: &mut Context<'_>) -> Poll<Self::Output>;
+}
+
+/// Tuple of futures to select.
+///
+/// It is implemented for tuples of futures up to 64 arity.
+pub trait IntoBranches {
+    /// Tuple of optional futures.
+    type Branches: Branches;
+
+    /// Wraps all futures as enabled branches.
+    fn into_branches(self) -> Self::Branches;
+}
+
+macro_rules! impl_branches {
+    ($(($f:ident $t:ident $v:ident $i:tt))*) => {
+        impl<$($f: Future),*> Branches for ($(Option<$f>,)*) {
+            type Output = Selected<$($f::Output),*>;
+
+            const LEN: usize = [$($i),*].len();
+
+            const DEFAULT: Self::Output = Selected::Default;
+
+            const COMPLETE: Self::Output = Selected::Complete;
+
+            type Wakes = WakeSet<{ [$($i),*].len() }, { wake_words([$($i),*].len()) }>;
+
+            fn is_terminated(&self, index: usize) -> bool {
+                match index {
+                    $($i => self.$i.is_none(),)*
+                    _ => unreachable!("branch index out of range"),
+                }
+            }
+
+            fn poll_branch(self: Pin<&mut Self>, index: usize, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                // SAFETY: futures are pinned in place until dropped.
+                let branches = unsafe { self.get_unchecked_mut() };
+                match index {
+                    $(
+                        $i => {
+                            let Some(future) = branches.$i.as_mut() else {
+                                return Poll::Pending;
+                            };
+                            let output = match unsafe { Pin::new_unchecked(future) }.poll(cx) {
+                                Poll::Pending => return Poll::Pending,
+                                Poll::Ready(output) => output,
+                            };
+                            branches.$i = None;
+                            Poll::Ready(Selected::$v(output))
+                        },
+                    )*
+                    _ => unreachable!("branch index out of range"),
+                }
+            }
+        }
+
+        impl<$($f: Future),*> IntoBranches for ($($f,)*) {
+            type Branches = ($(Option<$f>,)*);
+
+            fn into_branches(self) -> Self::Branches {
+                ($(Some(self.$i),)*)
+            }
+        }
+    };
+}
+
+macro_rules! impl_all_branches {
+    ([$($prefix:tt)*]) => {};
+    ([$($prefix:tt)*] $next:tt $($rest:tt)*) => {
+        impl_branches! { $($prefix)* $next }
+        impl_all_branches! { [$($prefix)* $next] $($rest)* }
+    };
+}
+
+impl_all_branches! {
+    []
+    (F0 T0 _0 0)
+    (F1 T1 _1 1)
+    (F2 T2 _2 2)
+    (F3 T3 _3 3)
+    (F4 T4 _4 4)
+    (F5 T5 _5 5)
+    (F6 T6 _6 6)
+    (F7 T7 _7 7)
+    (F8 T8 _8 8)
+    (F9 T9 _9 9)
+    (F10 T10 _10 10)
+    (F11 T11 _11 11)
+    (F12 T12 _12 12)
+    (F13 T13 _13 13)
+    (F14 T14 _14 14)
+    (F15 T15 _15 15)
+    (F16 T16 _16 16)
+    (F17 T17 _17 17)
+    (F18 T18 _18 18)
+    (F19 T19 _19 19)
+    (F20 T20 _20 20)
+    (F21 T21 _21 21)
+    (F22 T22 _22 22)
+    (F23 T23 _23 23)
+    (F24 T24 _24 24)
+    (F25 T25 _25 25)
+    (F26 T26 _26 26)
+    (F27 T27 _27 27)
+    (F28 T28 _28 28)
+    (F29 T29 _29 29)
+    (F30 T30 _30 30)
+    (F31 T31 _31 31)
+    (F32 T32 _32 32)
+    (F33 T33 _33 33)
+    (F34 T34 _34 34)
+    (F35 T35 _35 35)
+    (F36 T36 _36 36)
+    (F37 T37 _37 37)
+    (F38 T38 _38 38)
+    (F39 T39 _39 39)
+    (F40 T40 _40 40)
+    (F41 T41 _41 41)
+    (F42 T42 _42 42)
+    (F43 T43 _43 43)
+    (F44 T44 _44 44)
+    (F45 T45 _45 45)
+    (F46 T46 _46 46)
+    (F47 T47 _47 47)
+    (F48 T48 _48 48)
+    (F49 T49 _49 49)
+    (F50 T50 _50 50)
+    (F51 T51 _51 51)
+    (F52 T52 _52 52)
+    (F53 T53 _53 53)
+    (F54 T54 _54 54)
+    (F55 T55 _55 55)
+    (F56 T56 _56 56)
+    (F57 T57 _57 57)
+    (F58 T58 _58 58)
+    (F59 T59 _59 59)
+    (F60 T60 _60 60)
+    (F61 T61 _61 61)
+    (F62 T62 _62 62)
+    (F63 T63 _63 63)
+}
+
+/// Future to select the first ready one from a tuple of futures.
+///
+/// It is the library counterpart of [select!](crate::select!) without clauses. It resolves to
+/// [Selected] with the same semantics as `select!`.
+/// * [Select::biased] polls futures sequentially, otherwise the polling order is indeterminate.
+/// * [Select::nonblocking] resolves to [Selected::Default] if no futures are ready.
+/// * It resolves to [Selected::Complete] if all futures are disabled or completed.
+///
+/// Unlike ordinary futures, it could be polled again after ready to select among remaining futures,
+/// so it could be stored in structs and polled manually.
+///
+/// ```
+/// use core::future::{pending, ready};
+/// use async_select::{Select, Selected};
+///
+/// async fn select() {
+///     let mut select = Select::new((pending::<()>(), ready(5), ready("a"))).biased();
+///     assert_eq!((&mut select).await, Selected::_1(5));
+///     assert_eq!((&mut select).await, Selected::_2("a"));
+///     let mut select = select.nonblocking();
+///     assert_eq!((&mut select).await, Selected::Default);
+/// }
+/// ```
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Select<T: Branches> {
+    branches: T,
+    wakes: T::Wakes,
+    order: Order,
+    nonblocking: bool,
+}
+
+impl<T: Branches> Select<T> {
+    /// Constructs a select from a tuple of futures.
+    pub fn new<F: IntoBranches<Branches = T>>(futures: F) -> Self {
+        Self::conditional(futures.into_branches())
+    }
+
+    /// Constructs a select from a tuple of optional futures, `None`s are disabled branches.
+    pub fn conditional(branches: T) -> Self {
+        Self { branches, wakes: T::Wakes::new(), order: Order::Random, nonblocking: false }
+    }
+
+    /// Polls futures sequentially.
+    pub fn biased(self) -> Self {
+        Self { order: Order::Biased, ..self }
+    }
+
+    /// Polls futures from start branches generated from `seed`, so the polling order is reproducible.
+    pub fn seed(self, seed: u64) -> Self {
+        Self { order: Order::Seeded(Rng::new(seed)), ..self }
+    }
+
+    /// Resolves to [Selected::Default] instead of pending if no futures are ready.
+    pub fn nonblocking(self) -> Self {
+        Self { nonblocking: true, ..self }
+    }
+
+    /// Consumes this select to get remaining futures.
+    pub fn into_inner(self) -> T {
+        self.branches
+    }
+}
+
+impl<T: Branches> Select<T> {
+    /// Polls futures for output and index of the selected branch.
+    fn poll_select(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<(Option<usize>, T::Output)> {
+        // SAFETY: `branches` is pinned structurally and never moved.
+        let select = unsafe { self.get_unchecked_mut() };
+        let start = select.order.start(T::LEN);
+        let mut branches = unsafe { Pin::new_unchecked(&mut select.branches) };
+        let mut any_pending = false;
+        for i in 0..T::LEN {
+            let index = (start + i) % T::LEN;
+            if branches.is_terminated(index) {
+                continue;
+            }
+            match select.wakes.poll(index, cx, |cx| branches.as_mut().poll_branch(index, cx)) {
+                Poll::Pending => any_pending = true,
+                Poll::Ready(output) => return Poll::Ready((Some(index), output)),
+            }
+        }
+        if !any_pending {
+            Poll::Ready((None, T::COMPLETE))
+        } else if select.nonblocking {
+            Poll::Ready((None, T::DEFAULT))
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl<T: Branches> Future for Select<T> {
+    type Output = T::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.poll_select(cx).map(|(index, output)| {
+            index.inspect(|index| record(*index));
+            output
+        })
+    }
+}
+
+/// Selects with outputs filtered out by `filter` discarded, as if their branches are disabled.
+#[doc(hidden)]
+pub async fn select_filtered<T: Branches>(
+    select: Select<T>,
+    mut filter: impl FnMut(T::Output) -> Option<T::Output>,
+) -> T::Output {
+    let mut select = pin!(select);
+    poll_fn(|cx| loop {
+        let (index, output) = match select.as_mut().poll_select(cx) {
+            Poll::Pending => return Poll::Pending,
+            Poll::Ready(selected) => selected,
+        };
+        let Some(output) = filter(output) else {
+            continue;
+        };
+        index.inspect(|index| record(*index));
+        return Poll::Ready(output);
+    })
+    .await
+}
@@ -0,0 +1,198 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use crate::fairness::{Order, Rng};
+use crate::recording::record;
+use crate::waker::WakeSet;
+
+// Wakeups of futures beyond the first 64 are not tracked, as the array length is generic.
+const ARRAY_WAKE_WORDS: usize = 2;
+
+/// Future returned by [select_array].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct SelectArray<F, const N: usize> {
+    futures: [Option<F>; N],
+    wakes: WakeSet<N, ARRAY_WAKE_WORDS>,
+    order: Order,
+}
+
+/// Selects the first ready future from an array of futures.
+///
+/// It resolves to `(index, output, remaining)` where `remaining` holds all unselected futures with
+/// the selected one taken as `None`, which could be passed to [SelectArray::conditional] to select
+/// remaining futures. Futures are moved after polling, so they must be [Unpin].
+///
+/// The polling order is indeterminate as [select!](crate::select!) unless [SelectArray::biased].
+/// Wakeups of the first 64 futures are tracked, futures beyond are polled on every wakeup.
+///
+/// ## Panics
+/// * Panic if the array is empty.
+///
+/// ```
+/// use core::future::{pending, ready, Future};
+/// use core::pin::Pin;
+/// use async_select::select_array;
+///
+/// async fn select() {
+///     let futures: [Pin<Box<dyn Future<Output = i32>>>; 3] =
+///         [Box::pin(pending()), Box::pin(ready(5)), Box::pin(pending())];
+///     let (index, output, remaining) = select_array(futures).await;
+///     assert_eq!((index, output), (1, 5));
+///     assert!(remaining[1].is_none());
+/// }
+/// ```
+pub fn select_array<F: Future + Unpin, const N: usize>(futures: [F; N]) -> SelectArray<F, N> {
+    assert!(N != 0, "select_array: no futures");
+    SelectArray::conditional(futures.map(Some))
+}
+
+impl<F: Future + Unpin, const N: usize> SelectArray<F, N> {
+    /// Constructs a select from an array of optional futures, `None`s are skipped.
+    ///
+    /// ## Panics
+    /// * Panic if all futures are `None`.
+    ///
+    /// ```
+    /// use core::future::{pending, ready, Future};
+    /// use core::pin::Pin;
+    /// use async_select::{select_array, SelectArray};
+    ///
+    /// async fn select() {
+    ///     let futures: [Pin<Box<dyn Future<Output = i32>>>; 3] =
+    ///         [Box::pin(pending()), Box::pin(ready(5)), Box::pin(ready(6))];
+    ///     let (_, output, remaining) = select_array(futures).biased().await;
+    ///     assert_eq!(output, 5);
+    ///     let (index, output, _) = SelectArray::conditional(remaining).biased().await;
+    ///     assert_eq!((index, output), (2, 6));
+    /// }
+    /// ```
+    pub fn conditional(futures: [Option<F>; N]) -> Self {
+        assert!(futures.iter().any(Option::is_some), "select_array: no futures");
+        Self { futures, wakes: WakeSet::new(), order: Order::Random }
+    }
+}
+
+impl<F, const N: usize> SelectArray<F, N> {
+    /// Polls futures sequentially.
+    pub fn biased(self) -> Self {
+        Self { order: Order::Biased, ..self }
+    }
+
+    /// Polls futures from start branches generated from `seed`, so the polling order is reproducible.
+    pub fn seed(self, seed: u64) -> Self {
+        Self { order: Order::Seeded(Rng::new(seed)), ..self }
+    }
+}
+
+impl<F: Future + Unpin, const N: usize> Future for SelectArray<F, N> {
+    type Output = (usize, F::Output, [Option<F>; N]);
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let select = self.get_mut();
+        let start = select.order.start(N);
+        let mut completed = true;
+        for i in 0..N {
+            let index = (start + i) % N;
+            let Some(future) = select.futures[index].as_mut() else {
+                continue;
+            };
+            completed = false;
+            if let Poll::Ready(output) = select.wakes.poll(index, cx, |cx| Pin::new(future).poll(cx)) {
+                select.futures[index] = None;
+                record(index);
+                let remaining = core::mem::replace(&mut select.futures, core::array::from_fn(|_| None));
+                return Poll::Ready((index, output, remaining));
+            }
+        }
+        assert!(!completed, "select_array: polled after completion");
+        Poll::Pending
+    }
+}
+
+/// Future returned by [select_vec] and [select_iter].
+#[cfg(feature = "alloc")]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct SelectVec<F> {
+    futures: Vec<F>,
+    order: Order,
+}
+
+/// Selects the first ready future from a vector of futures.
+///
+/// It resolves to `(index, output, remaining)` where `remaining` holds all unselected futures in
+/// their original order. Futures are moved after polling, so they must be [Unpin].
+///
+/// The polling order is indeterminate as [select!](crate::select!) unless [SelectVec::biased].
+/// There is no waker tracking as the number of futures is unknown in compile time, so all futures
+/// are polled on every wakeup.
+///
+/// ## Panics
+/// * Panic if the vector is empty.
+///
+/// ```
+/// use core::future::{pending, ready, Future};
+/// use core::pin::Pin;
+/// use async_select::select_vec;
+///
+/// async fn select() {
+///     let futures: Vec<Pin<Box<dyn Future<Output = i32>>>> =
+///         vec![Box::pin(pending()), Box::pin(ready(5)), Box::pin(pending())];
+///     let (index, output, remaining) = select_vec(futures).await;
+///     assert_eq!((index, output), (1, 5));
+///     assert_eq!(remaining.len(), 2);
+/// }
+/// ```
+#[cfg(feature = "alloc")]
+pub fn select_vec<F: Future + Unpin>(futures: Vec<F>) -> SelectVec<F> {
+    assert!(!futures.is_empty(), "select_vec: no futures");
+    SelectVec { futures, order: Order::Random }
+}
+
+/// Selects the first ready future from an iterator of futures.
+///
+/// It collects futures into a vector for [select_vec].
+#[cfg(feature = "alloc")]
+pub fn select_iter<I>(futures: I) -> SelectVec<I::Item>
+where
+    I: IntoIterator,
+    I::Item: Future + Unpin, {
+    select_vec(futures.into_iter().collect())
+}
+
+#[cfg(feature = "alloc")]
+impl<F> SelectVec<F> {
+    /// Polls futures sequentially.
+    pub fn biased(self) -> Self {
+        Self { order: Order::Biased, ..self }
+    }
+
+    /// Polls futures from start branches generated from `seed`, so the polling order is reproducible.
+    pub fn seed(self, seed: u64) -> Self {
+        Self { order: Order::Seeded(Rng::new(seed)), ..self }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<F: Future + Unpin> Future for SelectVec<F> {
+    type Output = (usize, F::Output, Vec<F>);
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let select = self.get_mut();
+        assert!(!select.futures.is_empty(), "select_vec: polled after completion");
+        let n = select.futures.len();
+        let start = select.order.start(n);
+        for i in 0..n {
+            let index = (start + i) % n;
+            if let Poll::Ready(output) = Pin::new(&mut select.futures[index]).poll(cx) {
+                record(index);
+                let mut remaining = core::mem::take(&mut select.futures);
+                remaining.remove(index);
+                return Poll::Ready((index, output, remaining));
+            }
+        }
+        Poll::Pending
+    }
+}
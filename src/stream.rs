@@ -0,0 +1,39 @@
+use core::ops::DerefMut;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+/// Asynchronous sequence of values, which could be selected by `select!` in `pattern = stream in expr`.
+///
+/// It is shaped after `futures::Stream` and `core::async_iter::AsyncIterator`, so it is cheap to
+/// implement it for types from other ecosystems.
+pub trait Stream {
+    /// Values yielded by the stream.
+    type Item;
+
+    /// Attempts to pull out the next value of this stream.
+    ///
+    /// * `Poll::Pending` means that this stream's next value is not ready yet.
+    /// * `Poll::Ready(Some(item))` means that this stream has produced a value.
+    /// * `Poll::Ready(None)` means that this stream has terminated.
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>>;
+}
+
+impl<S: Stream + Unpin + ?Sized> Stream for &mut S {
+    type Item = S::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        S::poll_next(Pin::new(&mut **self), cx)
+    }
+}
+
+impl<P> Stream for Pin<P>
+where
+    P: DerefMut + Unpin,
+    P::Target: Stream,
+{
+    type Item = <P::Target as Stream>::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().as_mut().poll_next(cx)
+    }
+}
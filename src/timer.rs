@@ -0,0 +1,110 @@
+use core::future::Future;
+#[cfg(any(feature = "tokio", feature = "async-io"))]
+use core::pin::Pin;
+#[cfg(any(feature = "tokio", feature = "async-io"))]
+use core::task::{Context, Poll};
+use core::time::Duration;
+
+/// Timer of runtimes to back `timeout(duration) => ...` clause of `select!`.
+///
+/// Adapters are available behind cargo features:
+/// * `tokio`: `TokioTimer`
+/// * `async-io`: `AsyncIoTimer`
+/// * `smol`: `SmolTimer`
+///
+/// `timeout` uses [DefaultTimer] unless `timer = Type;` is specified.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a timer",
+    note = "enable one of features `tokio`, `async-io` and `smol` for default timer, or specify one with `timer = Type;`"
+)]
+pub trait Timer {
+    /// Future completes after some duration.
+    type Sleep: Future;
+
+    /// Constructs a future which completes after `duration`.
+    fn sleep(duration: Duration) -> Self::Sleep;
+}
+
+/// Timer backed by `tokio::time::sleep`.
+#[cfg(feature = "tokio")]
+pub struct TokioTimer;
+
+#[cfg(feature = "tokio")]
+impl Timer for TokioTimer {
+    type Sleep = tokio::time::Sleep;
+
+    fn sleep(duration: Duration) -> Self::Sleep {
+        tokio::time::sleep(duration)
+    }
+}
+
+/// Timer backed by `async_io::Timer`.
+#[cfg(feature = "async-io")]
+pub struct AsyncIoTimer;
+
+#[cfg(feature = "async-io")]
+impl Timer for AsyncIoTimer {
+    type Sleep = async_io::Timer;
+
+    fn sleep(duration: Duration) -> Self::Sleep {
+        async_io::Timer::after(duration)
+    }
+}
+
+/// Timer of `smol`, which is `async_io::Timer`.
+#[cfg(feature = "smol")]
+pub type SmolTimer = AsyncIoTimer;
+
+/// Timer used by `timeout` clause if there is no `timer = Type;`.
+///
+/// Runtime is picked on sleeping but not by cargo features, as features are unified across crates.
+/// It sleeps with `TokioTimer` in context of tokio runtime, otherwise `AsyncIoTimer` which needs no
+/// runtime. Without feature `async-io` or `smol`, it sleeps with `TokioTimer` anyway. It is not a
+/// [Timer] if none of features `tokio`, `async-io` and `smol` is enabled.
+pub struct DefaultTimer;
+
+/// Future returned by [DefaultTimer].
+#[cfg(any(feature = "tokio", feature = "async-io"))]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct DefaultSleep(SleepKind);
+
+#[cfg(any(feature = "tokio", feature = "async-io"))]
+enum SleepKind {
+    #[cfg(feature = "tokio")]
+    Tokio(tokio::time::Sleep),
+    #[cfg(feature = "async-io")]
+    AsyncIo(async_io::Timer),
+}
+
+#[cfg(any(feature = "tokio", feature = "async-io"))]
+impl Future for DefaultSleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        // SAFETY: sleep is pinned structurally and never moved.
+        match &mut unsafe { self.get_unchecked_mut() }.0 {
+            #[cfg(feature = "tokio")]
+            SleepKind::Tokio(sleep) => unsafe { Pin::new_unchecked(sleep) }.poll(cx),
+            #[cfg(feature = "async-io")]
+            SleepKind::AsyncIo(timer) => Pin::new(timer).poll(cx).map(|_| ()),
+        }
+    }
+}
+
+#[cfg(any(feature = "tokio", feature = "async-io"))]
+impl Timer for DefaultTimer {
+    type Sleep = DefaultSleep;
+
+    fn sleep(duration: Duration) -> Self::Sleep {
+        DefaultSleep(match () {
+            #[cfg(all(feature = "tokio", feature = "async-io"))]
+            () if tokio::runtime::Handle::try_current().is_err() => {
+                SleepKind::AsyncIo(async_io::Timer::after(duration))
+            },
+            #[cfg(feature = "tokio")]
+            () => SleepKind::Tokio(tokio::time::sleep(duration)),
+            #[cfg(not(feature = "tokio"))]
+            () => SleepKind::AsyncIo(async_io::Timer::after(duration)),
+        })
+    }
+}
@@ -0,0 +1,43 @@
+use core::task::{Context, Poll};
+
+#[cfg(target_has_atomic = "32")]
+mod tracked;
+#[cfg(not(target_has_atomic = "32"))]
+mod untracked;
+
+#[cfg(target_has_atomic = "32")]
+pub use tracked::WakeSet;
+#[cfg(not(target_has_atomic = "32"))]
+pub use untracked::WakeSet;
+
+const WORD_BITS: usize = u32::BITS as usize;
+
+/// Number of mask words to track wakeups of `branches` branches.
+pub const fn wake_words(branches: usize) -> usize {
+    branches.div_ceil(WORD_BITS)
+}
+
+impl<const N: usize, const W: usize> Default for WakeSet<N, W> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// [WakeSet] of any size, so it could be sized by the number of branches in generic code.
+pub trait Wakes: Unpin {
+    /// Constructs a set with all branches woken for their first polls.
+    fn new() -> Self;
+
+    /// Polls branch `index` through `f` with its branch waker, see [WakeSet::poll].
+    fn poll<T>(&self, index: usize, cx: &mut Context<'_>, f: impl FnOnce(&mut Context<'_>) -> Poll<T>) -> Poll<T>;
+}
+
+impl<const N: usize, const W: usize> Wakes for WakeSet<N, W> {
+    fn new() -> Self {
+        WakeSet::new()
+    }
+
+    fn poll<T>(&self, index: usize, cx: &mut Context<'_>, f: impl FnOnce(&mut Context<'_>) -> Poll<T>) -> Poll<T> {
+        WakeSet::poll(self, index, cx, f)
+    }
+}
@@ -0,0 +1,233 @@
+#[cfg(feature = "cached-waker")]
+use alloc::sync::Arc;
+#[cfg(not(feature = "cached-waker"))]
+use core::mem::ManuallyDrop;
+#[cfg(feature = "cached-waker")]
+use core::ptr;
+#[cfg(feature = "cached-waker")]
+use core::sync::atomic::{AtomicBool, AtomicPtr};
+use core::sync::atomic::{AtomicU32, Ordering};
+use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+use super::WORD_BITS;
+
+/// Wake flags of `select!` branches.
+///
+/// Each branch is polled with a waker wrapping the outer one, so a wakeup records the waking branch
+/// in a bitset before propagating. Branches not woken since their last poll are skipped. `W` words
+/// track wakeups of the first `W * 32` branches, branches beyond are polled on every wakeup.
+///
+/// A branch waker is borrowed from stack, so it lives only during its poll. With feature
+/// `cached-waker`, cloning it returns a waker cached for the branch, which still tells the branch
+/// after the poll. The cached waker is reused as long as the outer waker [Waker::will_wake] the same.
+/// Without `cached-waker`, a clone is merely a clone of the outer waker, so branches that clone their
+/// wakers are polled on every wakeup until rearmed.
+pub struct WakeSet<const N: usize, const W: usize> {
+    woken: [AtomicU32; W],
+    // Branches with wakers out there which can't tell them.
+    untracked: [AtomicU32; W],
+    // Wakers for clones of branch wakers, they are released only outside branch polls.
+    #[cfg(feature = "cached-waker")]
+    cached: [AtomicPtr<ClonedWaker>; N],
+}
+
+struct BranchWaker<'a> {
+    parent: &'a Waker,
+    woken: &'a AtomicU32,
+    bit: u32,
+    #[cfg(feature = "cached-waker")]
+    cached: &'a AtomicPtr<ClonedWaker>,
+    #[cfg(not(feature = "cached-waker"))]
+    untracked: &'a AtomicU32,
+}
+
+static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, wake, wake_by_ref, drop);
+
+#[cfg(feature = "cached-waker")]
+unsafe fn clone(data: *const ()) -> RawWaker {
+    let waker = unsafe { &*(data as *const BranchWaker<'_>) };
+    RawWaker::new(cached_waker(waker.cached, waker.parent) as *const (), &CLONED_VTABLE)
+}
+
+#[cfg(not(feature = "cached-waker"))]
+unsafe fn clone(data: *const ()) -> RawWaker {
+    let waker = unsafe { &*(data as *const BranchWaker<'_>) };
+    waker.untracked.fetch_or(waker.bit, Ordering::SeqCst);
+    let parent = ManuallyDrop::new(waker.parent.clone());
+    RawWaker::new(parent.data(), parent.vtable())
+}
+
+unsafe fn wake(data: *const ()) {
+    // Branch waker is borrowed from stack, so there is nothing to consume.
+    unsafe { wake_by_ref(data) }
+}
+
+unsafe fn wake_by_ref(data: *const ()) {
+    let waker = unsafe { &*(data as *const BranchWaker<'_>) };
+    waker.woken.fetch_or(waker.bit, Ordering::SeqCst);
+    waker.parent.wake_by_ref();
+}
+
+unsafe fn drop(_data: *const ()) {}
+
+/// Returns waker cached in `slot` with one more reference, caches one if there is none.
+#[cfg(feature = "cached-waker")]
+fn cached_waker(slot: &AtomicPtr<ClonedWaker>, parent: &Waker) -> *const ClonedWaker {
+    let mut cached = slot.load(Ordering::Acquire);
+    if cached.is_null() {
+        let waker = ClonedWaker { parent: parent.clone(), woken: AtomicBool::new(false) };
+        let allocated = Arc::into_raw(Arc::new(waker)) as *mut ClonedWaker;
+        cached = match slot.compare_exchange(ptr::null_mut(), allocated, Ordering::AcqRel, Ordering::Acquire) {
+            Ok(_) => allocated,
+            Err(existing) => {
+                release(allocated);
+                existing
+            },
+        };
+    }
+    // SAFETY: `slot` holds one reference, which is released only outside branch polls.
+    unsafe { Arc::increment_strong_count(cached) };
+    cached
+}
+
+/// Clone of branch waker which outlives the poll.
+#[cfg(feature = "cached-waker")]
+struct ClonedWaker {
+    parent: Waker,
+    woken: AtomicBool,
+}
+
+#[cfg(feature = "cached-waker")]
+static CLONED_VTABLE: RawWakerVTable = RawWakerVTable::new(cloned_clone, cloned_wake, cloned_wake_by_ref, cloned_drop);
+
+#[cfg(feature = "cached-waker")]
+unsafe fn cloned_clone(data: *const ()) -> RawWaker {
+    unsafe { Arc::increment_strong_count(data as *const ClonedWaker) };
+    RawWaker::new(data, &CLONED_VTABLE)
+}
+
+#[cfg(feature = "cached-waker")]
+unsafe fn cloned_wake(data: *const ()) {
+    let waker = unsafe { Arc::from_raw(data as *const ClonedWaker) };
+    waker.woken.store(true, Ordering::SeqCst);
+    waker.parent.wake_by_ref();
+}
+
+#[cfg(feature = "cached-waker")]
+unsafe fn cloned_wake_by_ref(data: *const ()) {
+    let waker = unsafe { &*(data as *const ClonedWaker) };
+    waker.woken.store(true, Ordering::SeqCst);
+    waker.parent.wake_by_ref();
+}
+
+#[cfg(feature = "cached-waker")]
+unsafe fn cloned_drop(data: *const ()) {
+    release(data as *mut ClonedWaker);
+}
+
+#[cfg(feature = "cached-waker")]
+fn release(waker: *mut ClonedWaker) {
+    if !waker.is_null() {
+        core::mem::drop(unsafe { Arc::from_raw(waker) });
+    }
+}
+
+/// Word index and bit of branch `index`.
+const fn locate(index: usize) -> (usize, u32) {
+    (index / WORD_BITS, 1 << (index % WORD_BITS))
+}
+
+impl<const N: usize, const W: usize> WakeSet<N, W> {
+    /// Constructs a set with all branches woken for their first polls.
+    pub const fn new() -> Self {
+        Self {
+            woken: [const { AtomicU32::new(u32::MAX) }; W],
+            untracked: [const { AtomicU32::new(0) }; W],
+            #[cfg(feature = "cached-waker")]
+            cached: [const { AtomicPtr::new(ptr::null_mut()) }; N],
+        }
+    }
+
+    /// Marks branch `index` as woken, so it will be polled in next round.
+    pub fn wake(&self, index: usize) {
+        let (word, bit) = locate(index);
+        if let Some(woken) = self.woken.get(word) {
+            woken.fetch_or(bit, Ordering::SeqCst);
+        }
+    }
+
+    /// Resets branch `index` for a newly armed future.
+    pub fn reset(&self, index: usize) {
+        let (word, bit) = locate(index);
+        if word < W {
+            self.woken[word].fetch_or(bit, Ordering::SeqCst);
+            // Wakers of previous future don't matter.
+            self.untracked[word].fetch_and(!bit, Ordering::SeqCst);
+        }
+    }
+
+    /// Takes wake flags of branch `index`.
+    fn take(&self, index: usize) -> bool {
+        let (word, bit) = locate(index);
+        // Clear flags before polling, so wakeups during or after polling will be observed in next round.
+        let woken = self.woken[word].fetch_and(!bit, Ordering::SeqCst) & bit != 0;
+        #[cfg(feature = "cached-waker")]
+        let woken = woken | {
+            let cached = self.cached[index].load(Ordering::Acquire);
+            !cached.is_null() && unsafe { &*cached }.woken.swap(false, Ordering::SeqCst)
+        };
+        woken || self.untracked[word].load(Ordering::SeqCst) & bit != 0
+    }
+
+    /// Releases cached waker of branch `index` if it does not wake `parent`. Its clones could still be
+    /// out there, so the branch is untracked until rearmed. Returns whether it is released, in which
+    /// case the branch must be polled to register `parent`.
+    #[cfg(feature = "cached-waker")]
+    fn refresh(&self, index: usize, parent: &Waker) -> bool {
+        let cached = self.cached[index].load(Ordering::Acquire);
+        if cached.is_null() || unsafe { &*cached }.parent.will_wake(parent) {
+            return false;
+        }
+        let (word, bit) = locate(index);
+        self.untracked[word].fetch_or(bit, Ordering::SeqCst);
+        release(self.cached[index].swap(ptr::null_mut(), Ordering::AcqRel));
+        true
+    }
+
+    /// Polls branch `index` through `f` with its branch waker, or returns [Poll::Pending] without polling
+    /// if the branch has not been woken since its last poll.
+    pub fn poll<T>(&self, index: usize, cx: &mut Context<'_>, f: impl FnOnce(&mut Context<'_>) -> Poll<T>) -> Poll<T> {
+        let (word, bit) = locate(index);
+        if word >= W {
+            return f(cx);
+        }
+        #[cfg(feature = "cached-waker")]
+        let refreshed = self.refresh(index, cx.waker());
+        #[cfg(not(feature = "cached-waker"))]
+        let refreshed = false;
+        // Take flags regardless, they are stale once the branch is polled.
+        if !(self.take(index) | refreshed) {
+            return Poll::Pending;
+        }
+        let branch = BranchWaker {
+            parent: cx.waker(),
+            woken: &self.woken[word],
+            bit,
+            #[cfg(feature = "cached-waker")]
+            cached: &self.cached[index],
+            #[cfg(not(feature = "cached-waker"))]
+            untracked: &self.untracked[word],
+        };
+        let waker = unsafe { Waker::from_raw(RawWaker::new(&branch as *const BranchWaker<'_> as *const (), &VTABLE)) };
+        f(&mut Context::from_waker(&waker))
+    }
+}
+
+#[cfg(feature = "cached-waker")]
+impl<const N: usize, const W: usize> Drop for WakeSet<N, W> {
+    fn drop(&mut self) {
+        for slot in self.cached.iter_mut() {
+            release(*slot.get_mut());
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use core::task::{Context, Poll};
+
+/// Wake flags of `select!` branches on targets without atomic read-modify-write.
+///
+/// Wakeups could come from other threads, so they can't be recorded without losing some. All
+/// branches are polled on every wakeup.
+pub struct WakeSet<const N: usize, const W: usize>;
+
+impl<const N: usize, const W: usize> WakeSet<N, W> {
+    /// Constructs a set with all branches woken for their first polls.
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Marks branch `index` as woken, it is a no-op as every branch is polled in next round.
+    pub fn wake(&self, _index: usize) {}
+
+    /// Resets branch `index` for a newly armed future, it is a no-op as no branch is tracked.
+    pub fn reset(&self, _index: usize) {}
+
+    /// Polls branch `index` through `f` with the outer waker.
+    pub fn poll<T>(&self, _index: usize, cx: &mut Context<'_>, f: impl FnOnce(&mut Context<'_>) -> Poll<T>) -> Poll<T> {
+        f(cx)
+    }
+}
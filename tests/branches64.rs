@@ -343,3 +343,84 @@ async fn blocking64<T>() -> T {
         _i = pending() => unreachable!(),
     }
 }
+
+#[tokio::test]
+async fn compact64() {
+    let v = select! {
+        compact;
+        complete => unreachable!(),
+
+        // 64 branches
+        r = ready(5) => r,
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+        _i = pending::<()>() => unreachable!(),
+    };
+    assert_eq!(v, 5);
+}
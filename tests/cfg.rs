@@ -0,0 +1,132 @@
+use std::future::{pending, ready};
+use std::task::{Context, Poll, Waker};
+
+use async_select::{join, poll_select, select, select_indexed, select_loop};
+
+#[tokio::test]
+async fn select_cfg_branch() {
+    let r = select! {
+        #[cfg(any())]
+        v = ready(5) => v,
+        #[cfg(all())]
+        v = ready(6) => v,
+        _ = pending::<()>() => unreachable!(),
+    };
+    assert_eq!(r, 6);
+}
+
+#[tokio::test]
+async fn select_cfg_all_disabled() {
+    let r = select! {
+        #[cfg(any())]
+        v = ready(5) => v,
+        Some(v) = ready(None) => v,
+        complete => 7,
+    };
+    assert_eq!(r, 7);
+}
+
+#[tokio::test]
+async fn select_cfg_clauses() {
+    let enabled = false;
+    let r = select! {
+        v = ready(5), if enabled => v,
+        #[cfg(any())]
+        complete => 6,
+        #[cfg(all())]
+        complete => 7,
+    };
+    assert_eq!(r, 7);
+    let r = select! {
+        _ = pending::<()>() => unreachable!(),
+        #[cfg(all())]
+        default => 6,
+        #[cfg(not(all()))]
+        default => 7,
+    };
+    assert_eq!(r, 6);
+}
+
+#[tokio::test]
+async fn select_cfg_options() {
+    for _ in 0..10 {
+        let r = select! {
+            biased;
+            #[cfg(any())]
+            _ = pending::<()>() => unreachable!(),
+            v = ready(5) => v,
+            v = ready(6) => v,
+        };
+        assert_eq!(r, 5);
+    }
+}
+
+#[tokio::test]
+async fn select_cfg_indexed() {
+    let (branch, v) = select_indexed! {
+        #[cfg(any())]
+        v = ready(5) => v,
+        v = ready(6) => v,
+        _ = pending::<()>() => unreachable!(),
+    };
+    assert_eq!(branch.index(), Some(0));
+    assert_eq!(v, 6);
+}
+
+#[tokio::test]
+async fn select_cfg_loop() {
+    let mut sum = 0;
+    let r = select_loop! {
+        #[cfg(any())]
+        v = repeat ready(5) => sum += v,
+        v = repeat ready(1) => {
+            sum += v;
+            if sum == 3 {
+                break sum;
+            }
+        },
+    };
+    assert_eq!(r, 3);
+}
+
+#[tokio::test]
+async fn select_attributes() {
+    let r = select! {
+        #[allow(unused_variables)]
+        v = ready(5) => 6,
+        _ = pending::<()>() => unreachable!(),
+    };
+    assert_eq!(r, 6);
+    let r = select! {
+        compact;
+        #[allow(unused_variables)]
+        v = ready(5) => 6,
+        _ = pending::<()>() => unreachable!(),
+    };
+    assert_eq!(r, 6);
+}
+
+#[test]
+fn poll_select_cfg() {
+    let mut cx = Context::from_waker(Waker::noop());
+    let r = poll_select!(&mut cx,
+        #[cfg(any())]
+        v = ready(5) => v,
+        _ = pending::<()>() => unreachable!(),
+        #[cfg(all())]
+        default => 6,
+    );
+    assert_eq!(r, Poll::Ready(6));
+}
+
+#[tokio::test]
+async fn join_cfg() {
+    let (a, b) = join! {
+        v = ready(5) => v,
+        #[cfg(any())]
+        v = ready(6) => v,
+        #[allow(unused_variables)]
+        v = ready(7), if true => 8,
+    };
+    assert_eq!((a, b), (5, Some(8)));
+}
@@ -0,0 +1,58 @@
+//! Fixtures shared among tests, each test uses some of them.
+#![allow(dead_code)]
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use async_select::Stream;
+
+/// Stream of given items, it wakes itself on [Poll::Pending].
+pub struct Iter {
+    items: VecDeque<Poll<i32>>,
+}
+
+impl Iter {
+    pub fn new(items: impl IntoIterator<Item = Poll<i32>>) -> Self {
+        Self { items: items.into_iter().collect() }
+    }
+
+    /// Constructs a stream of items which are all ready.
+    pub fn ready(items: impl IntoIterator<Item = i32>) -> Self {
+        Self::new(items.into_iter().map(Poll::Ready))
+    }
+}
+
+impl Stream for Iter {
+    type Item = i32;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        match self.items.pop_front() {
+            None => Poll::Ready(None),
+            Some(Poll::Ready(item)) => Poll::Ready(Some(item)),
+            Some(Poll::Pending) => {
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            },
+        }
+    }
+}
+
+/// Future which wakes itself `times` times before ready with `5`.
+pub struct YieldTimes {
+    pub times: usize,
+}
+
+impl Future for YieldTimes {
+    type Output = usize;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.times == 0 {
+            return Poll::Ready(5);
+        }
+        self.times -= 1;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
@@ -0,0 +1,104 @@
+use std::future::{pending, ready};
+
+use async_select::select;
+
+#[tokio::test]
+async fn compact_ready() {
+    let r = select! {
+        compact;
+        _ = pending::<()>() => unreachable!(),
+        v = ready(5) => v,
+    };
+    assert_eq!(r, 5);
+}
+
+#[tokio::test]
+async fn compact_biased() {
+    let r = select! {
+        biased;
+        compact;
+        v = ready(5) => v,
+        v = ready(6) => v,
+    };
+    assert_eq!(r, 5);
+}
+
+#[tokio::test]
+async fn compact_default() {
+    let r = select! {
+        compact;
+        _ = pending::<()>() => unreachable!(),
+        default => 5,
+    };
+    assert_eq!(r, 5);
+}
+
+fn none() -> Option<i32> {
+    None
+}
+
+#[tokio::test]
+async fn compact_condition() {
+    let opt: Option<i32> = none();
+    let r = select! {
+        compact;
+        v = ready(opt.unwrap()), if opt.is_some() => v,
+        v = ready(6) => v,
+    };
+    assert_eq!(r, 6);
+}
+
+#[tokio::test]
+async fn compact_refutable() {
+    let r = select! {
+        compact;
+        biased;
+        Some(v) = ready(None::<i32>) => v,
+        v = ready(6) => v,
+    };
+    assert_eq!(r, 6);
+}
+
+#[tokio::test]
+async fn compact_reference_and_guard() {
+    let numbers = [1, 2];
+    let r = select! {
+        compact;
+        biased;
+        &5 = ready(&4) => unreachable!(),
+        v = ready(String::from("a")) if v.is_empty() => v.len(),
+        &[1, v] = ready(&numbers[..]) => v,
+    };
+    assert_eq!(r, 2);
+}
+
+#[tokio::test]
+async fn compact_complete() {
+    let r = select! {
+        compact;
+        Some(v) = ready(None::<i32>) => v,
+        Some(v) = ready(None::<i32>) => v,
+        complete => 5,
+        default => 6,
+    };
+    assert_eq!(r, 5);
+}
+
+#[tokio::test]
+async fn compact_complete_default() {
+    let r = select! {
+        compact;
+        Some(v) = ready(None::<i32>) => v,
+        default => 6,
+    };
+    assert_eq!(r, 6);
+}
+
+#[tokio::test]
+#[should_panic(expected = "all branches are disabled or completed")]
+async fn compact_complete_panic() {
+    select! {
+        compact;
+        Some(v) = ready(None::<i32>) => v,
+    };
+}
@@ -0,0 +1,140 @@
+use std::collections::VecDeque;
+use std::future::{pending, ready};
+
+use async_select::{select, select_loop, try_select};
+
+mod common;
+
+use common::Iter;
+
+#[tokio::test]
+async fn else_disabled() {
+    let mut unmatched = vec![];
+    let r = select! {
+        biased;
+        Some(v) = ready(None::<i32>) => v,
+        else other => unmatched.push(other),
+        complete => 5,
+    };
+    assert_eq!(r, 5);
+    assert_eq!(unmatched, vec![None]);
+}
+
+#[tokio::test]
+async fn else_continue() {
+    let mut values = VecDeque::from([1, 2, 3, 4]);
+    let mut unmatched = vec![];
+    let r = select! {
+        4 = ready(values.pop_front().unwrap()) => 4,
+        else continue other => unmatched.push(other),
+        _ = pending::<()>() => unreachable!(),
+    };
+    assert_eq!(r, 4);
+    assert_eq!(unmatched, vec![1, 2, 3]);
+}
+
+#[tokio::test]
+async fn else_continue_silently() {
+    let mut values = VecDeque::from([1, 2, 3]);
+    let r = select! {
+        3 = ready(values.pop_front().unwrap()) => 3,
+        else continue,
+        _ = pending::<()>() => unreachable!(),
+    };
+    assert_eq!(r, 3);
+    assert!(values.is_empty());
+}
+
+#[tokio::test]
+async fn else_continue_borrowing_mutably() {
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    [1, 2, 3].into_iter().for_each(|v| tx.send(v).unwrap());
+    let mut unmatched = vec![];
+    let r = select! {
+        Some(3) = rx.recv() => 3,
+        else continue other => unmatched.push(other),
+        _ = pending::<()>() => unreachable!(),
+    };
+    assert_eq!(r, 3);
+    assert_eq!(unmatched, vec![Some(1), Some(2)]);
+}
+
+#[tokio::test]
+async fn else_labeled_continue() {
+    let mut selected = vec![];
+    'outer: for x in 0..3 {
+        let v = select! {
+            Some(v) = ready(if x == 1 { None } else { Some(x) }) => v,
+            else continue _ => continue 'outer,
+            _ = pending::<()>() => unreachable!(),
+        };
+        selected.push(v);
+    }
+    assert_eq!(selected, vec![0, 2]);
+}
+
+#[tokio::test]
+async fn else_block_clause() {
+    let mut count = 0;
+    let r = select! {
+        Some(v) = ready(None::<i32>) => { v }
+        else _ => {
+            count += 1;
+        }
+        complete => 5,
+    };
+    assert_eq!(r, 5);
+    assert_eq!(count, 1);
+}
+
+#[tokio::test]
+async fn else_stream_continue() {
+    let mut unmatched = vec![];
+    let r = select! {
+        5 = stream in Iter::ready([4, 3, 5, 6]) => 5,
+        else continue other => unmatched.push(other),
+        complete => 0,
+    };
+    assert_eq!(r, 5);
+    assert_eq!(unmatched, vec![4, 3]);
+}
+
+#[tokio::test]
+async fn else_stream_disabled() {
+    let mut unmatched = vec![];
+    let r = select! {
+        5 = stream in Iter::ready([4, 5]) => 5,
+        else other => unmatched.push(other),
+        complete => 0,
+    };
+    assert_eq!(r, 0);
+    assert_eq!(unmatched, vec![4]);
+}
+
+#[tokio::test]
+async fn else_in_loop() {
+    let mut values = VecDeque::from([1, 2, 3]);
+    let mut matched = vec![];
+    let mut unmatched = vec![];
+    select_loop! {
+        2 = ready(values.pop_front().unwrap()) => matched.push(2),
+        else continue other => unmatched.push(other),
+        complete => break,
+    };
+    assert_eq!(matched, vec![2]);
+    assert_eq!(unmatched, vec![1]);
+    assert_eq!(values, vec![3]);
+}
+
+#[tokio::test]
+async fn else_in_try_select() {
+    let mut results = VecDeque::from([Ok(1), Err(2)]);
+    let mut unmatched = vec![];
+    let r: Result<i32, i32> = try_select! {
+        5 = ready(results.pop_front().unwrap()) => 5,
+        else continue other => unmatched.push(other),
+        _ = pending::<Result<(), i32>>() => unreachable!(),
+    };
+    assert_eq!(r, Err(2));
+    assert_eq!(unmatched, vec![1]);
+}
@@ -0,0 +1,127 @@
+use std::future::ready;
+
+#[cfg(feature = "alloc")]
+use async_select::select_vec;
+use async_select::{select, select_loop, Select, Selected};
+
+const ROUNDS: usize = 4000;
+
+/// Counts selected branches in `ROUNDS` selections with all four branches ready.
+async fn distribution<F: std::future::Future<Output = usize>>(mut select: impl FnMut() -> F) -> [usize; 4] {
+    let mut counts = [0; 4];
+    for _ in 0..ROUNDS {
+        counts[select().await] += 1;
+    }
+    counts
+}
+
+/// Asserts that no branch is starved, that is, selected far less than its fair share.
+fn assert_no_starvation(counts: [usize; 4]) {
+    let share = ROUNDS / counts.len();
+    for (branch, count) in counts.into_iter().enumerate() {
+        assert!(count > share / 2, "branch {branch} is starved: {counts:?}");
+    }
+}
+
+#[tokio::test]
+async fn fair_random() {
+    let counts = distribution(|| async {
+        select! {
+            v = ready(0) => v,
+            v = ready(1) => v,
+            v = ready(2) => v,
+            v = ready(3) => v,
+        }
+    })
+    .await;
+    assert_no_starvation(counts);
+}
+
+#[tokio::test]
+async fn fair_random_explicitly() {
+    let counts = distribution(|| async {
+        select! {
+            fair = random;
+            v = ready(0) => v,
+            v = ready(1) => v,
+            v = ready(2) => v,
+            v = ready(3) => v,
+        }
+    })
+    .await;
+    assert_no_starvation(counts);
+}
+
+#[tokio::test]
+async fn fair_round_robin() {
+    let counts = distribution(|| async {
+        select! {
+            fair = round_robin;
+            v = ready(0) => v,
+            v = ready(1) => v,
+            v = ready(2) => v,
+            v = ready(3) => v,
+        }
+    })
+    .await;
+    assert_eq!(counts, [ROUNDS / 4; 4]);
+}
+
+#[tokio::test]
+async fn fair_round_robin_with_pending() {
+    let mut counts = [0; 3];
+    for _ in 0..300 {
+        let branch = select! {
+            fair = round_robin;
+            v = ready(0) => v,
+            v = ready(1) => v,
+            _ = std::future::pending::<()>() => unreachable!(),
+        };
+        counts[branch] += 1;
+    }
+    assert_eq!(counts, [150, 150, 0]);
+}
+
+#[tokio::test]
+async fn fair_loop() {
+    let mut counts = [0; 4];
+    let mut select = |branch: usize| {
+        counts[branch] += 1;
+        counts.iter().sum::<usize>() == ROUNDS
+    };
+    select_loop! {
+        fair = round_robin;
+        _ = repeat ready(()) => if select(0) { break },
+        _ = repeat ready(()) => if select(1) { break },
+        _ = repeat ready(()) => if select(2) { break },
+        _ = repeat ready(()) => if select(3) { break },
+    };
+    assert_no_starvation(counts);
+}
+
+#[tokio::test]
+async fn fair_select_future() {
+    let counts = distribution(|| async {
+        match Select::new((ready(0), ready(1), ready(2), ready(3))).await {
+            Selected::_0(v) | Selected::_1(v) | Selected::_2(v) | Selected::_3(v) => v,
+            _ => unreachable!(),
+        }
+    })
+    .await;
+    assert_no_starvation(counts);
+}
+
+#[cfg(feature = "alloc")]
+#[tokio::test]
+async fn fair_select_vec() {
+    let counts = distribution(|| async { select_vec((0..4).map(ready).collect()).await.1 }).await;
+    assert_no_starvation(counts);
+}
+
+#[tokio::test]
+async fn fair_as_pattern() {
+    let r = select! {
+        fair = ready(5) => fair,
+    };
+    assert_eq!(r, 5);
+}
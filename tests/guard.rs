@@ -0,0 +1,95 @@
+use std::collections::VecDeque;
+use std::future::{pending, ready};
+
+use async_select::{select, select_loop, try_select};
+
+#[tokio::test]
+async fn guard_passed() {
+    let r = select! {
+        Some(n) = ready(Some(5)) if n > 0 => n,
+        _ = pending::<()>() => unreachable!(),
+    };
+    assert_eq!(r, 5);
+}
+
+#[tokio::test]
+async fn guard_failed_disabled() {
+    let r = select! {
+        Some(n) = ready(Some(-5)) if n > 0 => n,
+        complete => 0,
+    };
+    assert_eq!(r, 0);
+}
+
+#[tokio::test]
+async fn guard_not_evaluated_on_mismatch() {
+    let mut evaluated = false;
+    let r = select! {
+        Some(n) = ready(None::<i32>) if {
+            evaluated = true;
+            n > 0
+        } => n,
+        complete => 0,
+    };
+    assert_eq!(r, 0);
+    assert!(!evaluated);
+}
+
+#[tokio::test]
+async fn guard_not_moving() {
+    let r = select! {
+        s = ready(String::from("async")) if s.starts_with("a") => s,
+        _ = pending::<()>() => unreachable!(),
+    };
+    assert_eq!(r, "async");
+}
+
+#[tokio::test]
+async fn guard_with_condition_and_weight() {
+    let enabled = true;
+    let r = select! {
+        n = ready(5), if enabled, weight 2 if n % 2 == 1 => n,
+        _ = pending::<()>() => unreachable!(),
+    };
+    assert_eq!(r, 5);
+}
+
+#[tokio::test]
+async fn guard_failed_else_continue() {
+    let mut values = VecDeque::from([-1, 0, 3]);
+    let mut skipped = vec![];
+    let r = select! {
+        n = ready(values.pop_front().unwrap()) if n > 0 => n,
+        else continue n => skipped.push(n),
+        _ = pending::<()>() => unreachable!(),
+    };
+    assert_eq!(r, 3);
+    assert_eq!(skipped, vec![-1, 0]);
+}
+
+#[tokio::test]
+async fn guard_in_loop() {
+    let mut values = VecDeque::from([1, 2, 3, 4]);
+    let mut evens = vec![];
+    select_loop! {
+        Some(n) = repeat ready(values.pop_front()), if !values.is_empty() if n % 2 == 0 => evens.push(n),
+        else continue,
+        complete => break,
+    };
+    assert_eq!(evens, vec![2, 4]);
+}
+
+#[tokio::test]
+async fn guard_in_try_select() {
+    let r: Result<i32, i32> = try_select! {
+        n = ready(Ok(-5)) if n > 0 => n,
+        complete => 0,
+    };
+    assert_eq!(r, Ok(0));
+
+    let r: Result<i32, i32> = try_select! {
+        n = ready(Err(5)) if n > 0 => n,
+        complete => 0,
+    };
+    assert_eq!(r, Err(5));
+}
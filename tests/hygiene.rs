@@ -0,0 +1,197 @@
+// Names used internally by expansions, they should be free to use in user code.
+#![allow(non_upper_case_globals)]
+
+use std::future::{pending, ready};
+use std::task::{Context, Poll, Waker};
+
+use async_select::{join, poll_select, select, select_indexed, select_loop, select_opt, try_join, try_select};
+
+const BRANCHES: usize = 100;
+const WEIGHTS: usize = 200;
+static ROUND_ROBIN: usize = 300;
+
+#[tokio::test]
+async fn select_internal_names() {
+    let output = 1;
+    let start = 2;
+    let i = 3;
+    let branch = 4;
+    let any_pending = 5;
+    let future = 6;
+    let cx = 7;
+    let __select_futures = 8;
+    let __select_matched = 9;
+    let r = select! {
+        v = ready(output + start), if i + branch > 0 => v + any_pending + future + cx,
+        v = ready(__select_futures), if __select_matched < 0 => v,
+        _ = pending::<usize>(), if __select_matched < 0 => unreachable!(),
+    };
+    assert_eq!(r, 1 + 2 + 5 + 6 + 7);
+}
+
+#[tokio::test]
+async fn select_internal_items() {
+    let r = select! {
+        v = ready(BRANCHES), weight 2 => v + WEIGHTS,
+        _ = pending::<()>() => unreachable!(),
+    };
+    assert_eq!(r, 300);
+    let r = select! {
+        fair = round_robin;
+        v = ready(ROUND_ROBIN) => v + BRANCHES,
+        _ = pending::<()>() => unreachable!(),
+    };
+    assert_eq!(r, 400);
+}
+
+#[tokio::test]
+async fn select_guard_names() {
+    let output = 1;
+    let __select_matched = false;
+    let r = select! {
+        v = ready(5) if v == output || __select_matched => v,
+        v = ready(6) if v > output => v + output,
+    };
+    assert_eq!(r, 7);
+}
+
+#[tokio::test]
+async fn select_repeat_names() {
+    let mut output = 0;
+    let __select_first = true;
+    let r = select! {
+        v = repeat ready(1), if output < 3 => output += v,
+        _ = ready(()), if !__select_first => unreachable!(),
+        complete => output,
+    };
+    assert_eq!(r, 3);
+}
+
+#[tokio::test]
+async fn select_else_names() {
+    let mut output = vec![];
+    let r = select! {
+        Some(v) = ready(None::<usize>) => v,
+        else other => output.push(other),
+        complete => output.len(),
+    };
+    assert_eq!(r, 1);
+}
+
+#[tokio::test]
+async fn select_keep_names() {
+    let output = 5;
+    let (v, _future) = select! {
+        keep (_, __select_futures);
+        v = ready(output) => (v, __select_futures),
+        _ = pending::<i32>() => unreachable!(),
+    };
+    assert_eq!(v, 5);
+}
+
+#[tokio::test]
+async fn select_seed_names() {
+    let __select_rng = 5;
+    let r = select! {
+        seed = 3;
+        v = ready(__select_rng) => v + BRANCHES,
+        _ = pending::<()>() => unreachable!(),
+    };
+    assert_eq!(r, 105);
+}
+
+#[tokio::test]
+async fn select_compact_names() {
+    let output = 1;
+    let __select_matched = 2;
+    let r = select! {
+        compact;
+        v = ready(output) if v > 0 => v + output + __select_matched + BRANCHES,
+        _ = pending::<()>() => unreachable!(),
+    };
+    assert_eq!(r, 104);
+}
+
+#[tokio::test]
+async fn select_nopanic_names() {
+    let __select_enabled = false;
+    let r = select_opt! {
+        Some(v) = ready(None::<usize>), if !__select_enabled => v,
+    };
+    assert_eq!(r, None);
+}
+
+#[tokio::test]
+async fn select_report_names() {
+    let __select_report = 5;
+    let r = select! {
+        Some(v) = ready(None::<usize>) => v,
+        complete(report) => report.len() + __select_report,
+    };
+    assert_eq!(r, 6);
+}
+
+#[tokio::test]
+async fn select_loop_names() {
+    let mut output = 0;
+    let i = 3;
+    let r = select_loop! {
+        v = repeat ready(1) => {
+            output += v;
+            if output == i {
+                break output + BRANCHES;
+            }
+        },
+    };
+    assert_eq!(r, 103);
+}
+
+#[tokio::test]
+async fn try_select_names() {
+    let output = 1;
+    let r: Result<usize, ()> = try_select! {
+        v = ready(Ok(output)) => v + BRANCHES,
+        _ = pending::<Result<(), ()>>() => unreachable!(),
+    };
+    assert_eq!(r, Ok(101));
+}
+
+#[tokio::test]
+async fn select_indexed_names() {
+    let output = 1;
+    let (branch, v) = select_indexed! {
+        v = ready(output) => v + BRANCHES,
+        _ = pending::<()>() => unreachable!(),
+    };
+    assert_eq!(branch.index(), Some(0));
+    assert_eq!(v, 101);
+}
+
+#[test]
+fn poll_select_names() {
+    let mut context = Context::from_waker(Waker::noop());
+    let cx = 1;
+    let output = 2;
+    let r = poll_select!(&mut context,
+        v = ready(output) => v + cx + BRANCHES,
+        _ = pending::<()>() => unreachable!(),
+    );
+    assert_eq!(r, Poll::Ready(103));
+}
+
+#[tokio::test]
+async fn join_names() {
+    let output = 1;
+    let any_pending = 2;
+    let __join_outputs = 3;
+    let (a, b) = join! {
+        v = ready(output) => v + any_pending,
+        v = ready(__join_outputs), if BRANCHES > 0 => v + BRANCHES,
+    };
+    assert_eq!((a, b), (3, Some(103)));
+    let joined = 4;
+    let r = try_join! {
+        v = ready(Ok::<usize, ()>(joined)) => v + output,
+    };
+    assert_eq!(r, Ok((5,)));
+}
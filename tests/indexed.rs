@@ -0,0 +1,99 @@
+use std::future::{pending, ready};
+
+use async_select::{select, select_indexed, SelectedBranch};
+
+#[tokio::test]
+async fn indexed_unlabeled() {
+    let (branch, v) = select_indexed! {
+        _ = pending::<()>() => unreachable!(),
+        v = ready(5) => v,
+    };
+    assert_eq!(branch, SelectedBranch::Future { index: 1, label: None });
+    assert_eq!(branch.index(), Some(1));
+    assert_eq!(branch.label(), None);
+    assert_eq!(v, 5);
+}
+
+#[tokio::test]
+async fn indexed_labeled() {
+    let (branch, v) = select_indexed! {
+        biased;
+        'first: _ = pending::<()>() => unreachable!(),
+        'second: v = ready(5) => v,
+        'third: v = ready(6) => v,
+    };
+    assert_eq!(branch, SelectedBranch::Future { index: 1, label: Some("second") });
+    assert_eq!(v, 5);
+}
+
+#[tokio::test]
+async fn indexed_default() {
+    let (branch, v) = select_indexed! {
+        _ = pending::<()>() => unreachable!(),
+        default => 5,
+    };
+    assert_eq!(branch, SelectedBranch::Default);
+    assert_eq!(branch.index(), None);
+    assert_eq!(branch.label(), Some("default"));
+    assert_eq!(v, 5);
+}
+
+#[tokio::test]
+async fn indexed_complete() {
+    let (branch, v) = select_indexed! {
+        Some(_) = ready(None::<i32>) => unreachable!(),
+        complete => 5,
+    };
+    assert_eq!(branch, SelectedBranch::Complete);
+    assert_eq!(v, 5);
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn indexed_timeout() {
+    let (branch, v) = select_indexed! {
+        _ = pending::<()>() => unreachable!(),
+        timeout(std::time::Duration::from_millis(1)) => 5,
+    };
+    assert_eq!(branch, SelectedBranch::Timeout);
+    assert_eq!(v, 5);
+}
+
+#[tokio::test]
+async fn indexed_repeat() {
+    let mut i = 0;
+    let (branch, v) = select_indexed! {
+        'repeat: _ = repeat ready(()), if i < 3 => i += 1,
+        complete => i,
+    };
+    assert_eq!(branch, SelectedBranch::Complete);
+    assert_eq!(v, 3);
+}
+
+#[tokio::test]
+async fn labels_in_select() {
+    let r = select! {
+        'pending: _ = pending::<()>() => unreachable!(),
+        'ready: v = ready(5) => v,
+    };
+    assert_eq!(r, 5);
+}
+
+#[tokio::test]
+async fn indexed_compact() {
+    let (branch, v) = select_indexed! {
+        compact;
+        'pending: _ = pending::<()>() => unreachable!(),
+        'ready: v = ready(5) => v,
+    };
+    assert_eq!(branch, SelectedBranch::Future { index: 1, label: Some("ready") });
+    assert_eq!(v, 5);
+
+    let (branch, v) = select_indexed! {
+        compact;
+        Some(_) = ready(None::<i32>) => unreachable!(),
+        default => 6,
+    };
+    assert_eq!(branch, SelectedBranch::Default);
+    assert_eq!(v, 6);
+}
@@ -0,0 +1,105 @@
+use std::cell::Cell;
+use std::future::{pending, ready};
+use std::time::Duration;
+
+use async_select::{join, try_join};
+
+#[tokio::test]
+async fn join_all() {
+    let (a, b) = join! {
+        v = ready(5) => v,
+        v = ready("a") => v,
+    };
+    assert_eq!(a, 5);
+    assert_eq!(b, "a");
+}
+
+#[tokio::test]
+async fn join_concurrently() {
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    let (a, b) = join! {
+        v = receiver => v.unwrap(),
+        _ = async {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+            sender.send(5).unwrap();
+        } => 6,
+    };
+    assert_eq!((a, b), (5, 6));
+}
+
+#[tokio::test]
+async fn join_condition() {
+    fn none() -> Option<i32> {
+        None
+    }
+    let opt = none();
+    let (a, b) = join! {
+        v = ready(opt.unwrap()), if opt.is_some() => v,
+        v = ready(6) => v,
+    };
+    assert_eq!(a, None);
+    assert_eq!(b, 6);
+
+    let (a,) = join! {
+        v = ready(5), if true => v,
+    };
+    assert_eq!(a, Some(5));
+}
+
+#[tokio::test]
+async fn join_clauses_in_order() {
+    let order = Cell::new(0);
+    let (a, b) = join! {
+        _ = tokio::time::sleep(Duration::from_millis(5)) => order.replace(order.get() + 1),
+        _ = ready(()) => order.replace(order.get() + 1),
+    };
+    assert_eq!((a, b), (0, 1));
+}
+
+#[tokio::test]
+async fn join_patterns() {
+    let ((a, b), mut c) = join! {
+        (a, b) = ready((1, 2)) => (a, b),
+        mut v = ready(vec![3]) => {
+            v.push(4);
+            v
+        },
+    };
+    c.push(5);
+    assert_eq!((a, b), (1, 2));
+    assert_eq!(c, vec![3, 4, 5]);
+}
+
+#[tokio::test]
+async fn try_join_ok() {
+    let r = try_join! {
+        v = ready(Ok::<i32, &str>(5)) => v,
+        v = ready(Ok("a")) => v,
+        v = ready(Ok(6)), if false => v,
+    };
+    assert_eq!(r, Ok((5, "a", None)));
+}
+
+#[tokio::test]
+async fn try_join_err() {
+    let r = try_join! {
+        v = pending::<Result<i32, &str>>() => v,
+        v = ready(Err::<i32, &str>("failure")) => v,
+    };
+    assert_eq!(r, Err("failure"));
+}
+
+#[tokio::test]
+async fn try_join_err_first() {
+    let r = try_join! {
+        v = async {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+            Err::<i32, i32>(1)
+        } => v,
+        v = async {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            Err::<i32, i32>(2)
+        } => v,
+    };
+    assert_eq!(r, Err(1));
+}
@@ -0,0 +1,80 @@
+use std::future::{pending, ready};
+use std::pin::pin;
+
+use async_select::select;
+
+mod common;
+
+use common::YieldTimes;
+
+#[tokio::test]
+async fn keep_unselected() {
+    let (r, future) = select! {
+        biased;
+        keep (_, future);
+        v = ready(1) => (v, future),
+        v = YieldTimes { times: 3 } => (v, future),
+    };
+    assert_eq!(r, 1);
+    let mut future = future.unwrap();
+    let r = select! {
+        v = &mut future => v,
+        default => 0,
+    };
+    assert_eq!(r, 0);
+    assert_eq!(future.times, 2);
+    assert_eq!(future.await, 5);
+}
+
+#[tokio::test]
+async fn keep_selected_none() {
+    let futures = select! {
+        keep futures;
+        _ = ready(1) => futures,
+        _ = pending::<()>() => unreachable!(),
+    };
+    assert!(futures.0.is_none());
+    assert!(futures.1.is_some());
+}
+
+#[tokio::test]
+async fn keep_default() {
+    let (a, b) = select! {
+        keep (a, b);
+        _ = pending::<()>() => unreachable!(),
+        _ = pending::<()>() => unreachable!(),
+        default => (a, b),
+    };
+    assert!(a.is_some());
+    assert!(b.is_some());
+}
+
+fn none() -> Option<i32> {
+    None
+}
+
+#[tokio::test]
+async fn keep_complete() {
+    let opt: Option<i32> = none();
+    let (a, b) = select! {
+        keep (a, b);
+        biased;
+        _ = ready(opt.unwrap()), if opt.is_some() => unreachable!(),
+        Some(_) = ready(None::<i32>) => unreachable!(),
+        complete => (a, b),
+    };
+    assert!(a.is_none());
+    assert!(b.is_none());
+}
+
+#[tokio::test]
+async fn keep_pinned() {
+    let mut future = pin!(YieldTimes { times: 1 });
+    let r = select! {
+        keep _futures;
+        v = future.as_mut() => v,
+        default => 0,
+    };
+    assert_eq!(r, 0);
+    assert_eq!(future.await, 5);
+}
@@ -0,0 +1,149 @@
+use std::future::{pending, ready};
+use std::task::{Context, Poll, Waker};
+
+use async_select::{poll_select, select, select_indexed, select_opt, try_select, SelectError};
+
+#[tokio::test]
+async fn select_nopanic_disabled() {
+    let enabled = false;
+    let r = select! {
+        nopanic;
+        v = ready(5), if enabled => v,
+        v = ready(6), if enabled => v,
+    };
+    assert_eq!(r, Err(SelectError::Disabled));
+}
+
+#[tokio::test]
+async fn select_nopanic_completed() {
+    let enabled = false;
+    let r = select! {
+        nopanic;
+        v = ready(5), if enabled => v,
+        Some(v) = ready(None) => v,
+        v = ready(6) if v > 6 => v,
+    };
+    assert_eq!(r, Err(SelectError::Completed));
+}
+
+#[tokio::test]
+async fn select_nopanic_selected() {
+    let r = select! {
+        nopanic;
+        Some(v) = ready(None) => v,
+        v = ready(6) => v,
+    };
+    assert_eq!(r, Ok(6));
+}
+
+#[tokio::test]
+async fn select_nopanic_clauses() {
+    let r = select! {
+        nopanic;
+        _ = pending::<()>() => unreachable!(),
+        default => 5,
+    };
+    assert_eq!(r, Ok(5));
+    let r = select! {
+        nopanic;
+        Some(v) = ready(None) => v,
+        complete => 6,
+    };
+    assert_eq!(r, Ok(6));
+}
+
+#[tokio::test]
+async fn select_nopanic_repeat() {
+    let mut sum = 0;
+    let r: Result<(), _> = select! {
+        nopanic;
+        v = repeat ready(1), if sum < 3 => sum += v,
+    };
+    assert_eq!(r, Err(SelectError::Completed));
+    assert_eq!(sum, 3);
+}
+
+#[tokio::test]
+async fn select_nopanic_compact() {
+    let enabled = false;
+    let r = select! {
+        nopanic;
+        compact;
+        v = ready(5), if enabled => v,
+    };
+    assert_eq!(r, Err(SelectError::Disabled));
+    let r = select! {
+        compact;
+        nopanic;
+        Some(v) = ready(None::<i32>) => v,
+    };
+    assert_eq!(r, Err(SelectError::Completed));
+}
+
+#[tokio::test]
+async fn select_nopanic_keep() {
+    let r = select! {
+        nopanic;
+        keep (future,);
+        Some(v) = ready(None::<i32>) => (v, future.is_some()),
+    };
+    assert_eq!(r, Err(SelectError::Completed));
+}
+
+#[tokio::test]
+async fn select_nopanic_indexed() {
+    let (branch, r) = select_indexed! {
+        nopanic;
+        Some(v) = ready(None::<i32>) => v,
+    };
+    assert_eq!(branch.index(), None);
+    assert_eq!(r, Err(SelectError::Completed));
+}
+
+#[tokio::test]
+async fn try_select_nopanic() {
+    let r = try_select! {
+        nopanic;
+        Some(v) = ready(Ok::<_, ()>(None::<i32>)) => v,
+    };
+    assert_eq!(r, Err(SelectError::Completed));
+    let r = try_select! {
+        nopanic;
+        v = ready(Err::<i32, _>(5)) => v,
+        Some(v) = ready(Ok(None)) => v,
+    };
+    assert_eq!(r, Ok(Err(5)));
+    let r: Result<Result<i32, ()>, _> = try_select! {
+        nopanic;
+        v = ready(Err(5)) => v,
+        error(e) => Ok(e),
+    };
+    assert_eq!(r, Ok(Ok(5)));
+}
+
+#[test]
+fn poll_select_nopanic() {
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut enabled = true;
+    let r = poll_select!(&mut cx,
+        nopanic;
+        Some(v) = ready(None::<i32>), if enabled => v,
+        else _ => enabled = false,
+    );
+    assert_eq!(r, Poll::Ready(Err(SelectError::Completed)));
+}
+
+#[tokio::test]
+async fn select_opt() {
+    let enabled = false;
+    let r = select_opt! {
+        v = ready(5), if enabled => v,
+    };
+    assert_eq!(r, None);
+    let r = select_opt! {
+        biased;
+        v = ready(5) => v,
+        v = ready(6) => v,
+    };
+    assert_eq!(r, Some(5));
+}
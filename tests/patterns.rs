@@ -17,7 +17,10 @@ struct TupleStruct((), (), ());
 // failure will cause compilation error.
 #[allow(dead_code)]
 #[allow(unused_parens)]
+#[allow(clippy::needless_borrowed_reference)]
 async fn patterns() {
+    let mut n = 5;
+    let strings = [String::new()];
     select! {
         default => {},
         complete => {},
@@ -55,17 +58,17 @@ async fn patterns() {
         1..=2 = ready(5) => {},
 
         // Reference(PatReference)
-        //
-        // This is not supported as we are pattern against value.
-        // &_v = ready(5) => {}
-        // &mut _v = ready(5) => {}
+        &_v = ready(&5) => {},
+        &5 = ready(&5) => {},
+        &mut ref mut _v = ready(&mut n) => {},
 
         // Rest(PatRest)
         (ref _i, mut _v, ..) = ready((1, 2, 3, 4)) => {},
 
         // Slice(PatSlice)
-        //
-        // Pattern against value but not reference.
+        [_a, .., _b] = ready([1, 2, 3]) => {},
+        &[ref _a, _] = ready(&strings[..]) => {},
+        [ref mut _a, _b @ ..] = ready([String::new(), String::new()]) => {},
 
         // Struct(PatStruct)
         FieldStruct { ref mut _a, ref _b, .. } = ready(FieldStruct::default()) => {},
@@ -88,3 +91,47 @@ async fn patterns() {
         _ = ready(()) => {}
     }
 }
+
+#[tokio::test]
+async fn reference_patterns() {
+    let r = select! {
+        &5 = ready(&4) => unreachable!(),
+        &v = ready(&5) => v,
+    };
+    assert_eq!(r, 5);
+
+    let mut n = 5;
+    let r = select! {
+        &mut ref mut v = ready(&mut n) => {
+            *v += 1;
+            *v
+        },
+    };
+    assert_eq!(r, 6);
+    assert_eq!(n, 6);
+}
+
+#[tokio::test]
+#[allow(clippy::needless_borrowed_reference)]
+async fn slice_patterns() {
+    let strings = [String::from("a"), String::from("b"), String::from("c")];
+    let r = select! {
+        biased;
+        &[ref only] = ready(&strings[..]) => only.clone(),
+        &[ref first, _, ref last] = ready(&strings[..]) => format!("{first}{last}"),
+    };
+    assert_eq!(r, "ac");
+
+    let r = select! {
+        [first, rest @ ..] = ready(strings.clone()) => first + &rest.concat(),
+    };
+    assert_eq!(r, "abc");
+
+    let numbers = vec![1, 2, 3];
+    let r = select! {
+        biased;
+        &[4, ..] = ready(numbers.as_slice()) => unreachable!(),
+        &[_, second, ..] = ready(numbers.as_slice()) => second,
+    };
+    assert_eq!(r, 2);
+}
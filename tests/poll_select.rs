@@ -0,0 +1,167 @@
+use std::future::{pending, ready, Future, Pending, Ready};
+use std::pin::Pin;
+use std::task::{Context, Poll, Waker};
+
+use async_select::poll_select;
+use tokio::sync::oneshot;
+
+mod common;
+
+use common::Iter;
+
+struct Race {
+    fast: Option<Ready<i32>>,
+    slow: Pending<i32>,
+}
+
+impl Future for Race {
+    type Output = i32;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<i32> {
+        let this = &mut *self;
+        poll_select!(cx,
+            v = this.fast.as_mut().unwrap(), if this.fast.is_some() => {
+                this.fast = None;
+                v
+            },
+            v = &mut this.slow => v,
+        )
+    }
+}
+
+#[tokio::test]
+async fn poll_select_ready() {
+    let r = Race { fast: Some(ready(5)), slow: pending() }.await;
+    assert_eq!(r, 5);
+}
+
+struct Receivers {
+    a: Option<oneshot::Receiver<i32>>,
+    b: Option<Pin<Box<oneshot::Receiver<i32>>>>,
+}
+
+impl Receivers {
+    fn new(a: oneshot::Receiver<i32>, b: oneshot::Receiver<i32>) -> Self {
+        Self { a: Some(a), b: Some(Box::pin(b)) }
+    }
+}
+
+impl Future for Receivers {
+    type Output = i32;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<i32> {
+        let this = &mut *self;
+        poll_select!(cx,
+            Ok(v) = this.a.as_mut().unwrap(), if this.a.is_some() => v,
+            else _ => this.a = None,
+            Ok(v) = this.b.as_mut().unwrap().as_mut(), if this.b.is_some() => v * 2,
+            else _ => this.b = None,
+            complete => 0,
+        )
+    }
+}
+
+#[tokio::test]
+async fn poll_select_pending() {
+    let (_sender_a, a) = oneshot::channel();
+    let (sender_b, b) = oneshot::channel();
+    let mut receivers = Receivers::new(a, b);
+    let mut cx = Context::from_waker(Waker::noop());
+    assert_eq!(Pin::new(&mut receivers).poll(&mut cx), Poll::Pending);
+    tokio::spawn(async move { sender_b.send(5).unwrap() });
+    assert_eq!(receivers.await, 10);
+}
+
+#[tokio::test]
+async fn poll_select_else_disabling() {
+    let (sender_a, a) = oneshot::channel();
+    let (sender_b, b) = oneshot::channel();
+    let mut receivers = Receivers::new(a, b);
+    drop(sender_a);
+    let mut cx = Context::from_waker(Waker::noop());
+    assert_eq!(Pin::new(&mut receivers).poll(&mut cx), Poll::Pending);
+    assert!(receivers.a.is_none());
+    // Completed receiver must not be polled again.
+    assert_eq!(Pin::new(&mut receivers).poll(&mut cx), Poll::Pending);
+    tokio::spawn(async move { sender_b.send(5).unwrap() });
+    assert_eq!(receivers.await, 10);
+}
+
+#[test]
+fn poll_select_else_complete() {
+    let (sender_a, a) = oneshot::channel();
+    let (sender_b, b) = oneshot::channel();
+    let mut receivers = Receivers::new(a, b);
+    drop(sender_a);
+    drop(sender_b);
+    let mut cx = Context::from_waker(Waker::noop());
+    assert_eq!(Pin::new(&mut receivers).poll(&mut cx), Poll::Ready(0));
+}
+
+#[test]
+fn poll_select_default() {
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut future = pending::<i32>();
+    let r = poll_select!(&mut cx,
+        v = &mut future => v,
+        default => 6,
+    );
+    assert_eq!(r, Poll::Ready(6));
+}
+
+#[test]
+fn poll_select_complete() {
+    let mut cx = Context::from_waker(Waker::noop());
+    let enabled = false;
+    let (mut some, mut large) = (true, true);
+    let r = poll_select!(&mut cx,
+        v = ready(5), if enabled => v,
+        Some(v) = ready(None), if some => v,
+        else _ => some = false,
+        v = ready(6), if large if v > 6 => v,
+        else _ => large = false,
+        complete => 7,
+    );
+    assert_eq!(r, Poll::Ready(7));
+    assert!(!some && !large);
+}
+
+#[test]
+fn poll_select_or_pattern() {
+    let mut cx = Context::from_waker(Waker::noop());
+    let r = poll_select!(&mut cx,
+        (Ok(v), _) | (Err(_), v) = ready((Err::<i32, ()>(()), 5)) => v,
+    );
+    assert_eq!(r, Poll::Ready(5));
+}
+
+#[test]
+fn poll_select_biased() {
+    let mut cx = Context::from_waker(Waker::noop());
+    for _ in 0..10 {
+        let r = poll_select!(&mut cx,
+            biased;
+            _ = pending::<()>() => unreachable!(),
+            v = ready(5) => v,
+            v = ready(6) => v,
+        );
+        assert_eq!(r, Poll::Ready(5));
+    }
+}
+
+#[test]
+fn poll_select_stream() {
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut iter = Iter::ready([1, 2]);
+    let mut sum = 0;
+    loop {
+        match poll_select!(&mut cx,
+            v = stream in &mut iter => v,
+            complete => break,
+        ) {
+            Poll::Ready(v) => sum += v,
+            Poll::Pending => unreachable!(),
+        }
+    }
+    assert_eq!(sum, 3);
+}
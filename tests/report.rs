@@ -0,0 +1,91 @@
+use std::collections::VecDeque;
+use std::future::{pending, ready};
+use std::task::{Context, Poll, Waker};
+
+use async_select::{poll_select, select, select_loop, BranchEnd, CompleteReport};
+
+mod common;
+
+use common::Iter;
+
+#[tokio::test]
+async fn complete_report() {
+    let enabled = false;
+    let report = select! {
+        _ = ready(5), if enabled => unreachable!(),
+        Some(_) = ready(None::<i32>) => unreachable!(),
+        _v = ready(6) if _v > 6 => unreachable!(),
+        _ = stream in Iter::ready([1]), if enabled => unreachable!(),
+        _ = stream in Iter::ready([]) => unreachable!(),
+        complete(report) => report,
+    };
+    let report: CompleteReport<5> = report;
+    assert_eq!(report.len(), 5);
+    assert_eq!(report.iter().collect::<Vec<_>>(), vec![
+        BranchEnd::Disabled,
+        BranchEnd::Mismatched,
+        BranchEnd::Mismatched,
+        BranchEnd::Disabled,
+        BranchEnd::Ended,
+    ]);
+}
+
+#[tokio::test]
+async fn complete_report_selected() {
+    let mut sum = 0;
+    let report = select_loop! {
+        v = ready(1) => sum += v,
+        Some(v) = ready(None::<i32>) => sum += v,
+        v = repeat ready(2), if sum < 4 => sum += v,
+        complete(report) => break report,
+    };
+    assert_eq!(sum, 5);
+    assert_eq!(report.get(0), Some(BranchEnd::Selected));
+    assert_eq!(report.get(1), Some(BranchEnd::Mismatched));
+    assert_eq!(report.get(2), Some(BranchEnd::Disabled));
+    assert_eq!(report.get(3), None);
+}
+
+#[tokio::test]
+async fn complete_report_else() {
+    let mut skipped = 0;
+    let mut items = VecDeque::from([None::<i32>, None]);
+    let report = select! {
+        Some(_) = ready(items.pop_front().unwrap()), if !items.is_empty() => unreachable!(),
+        else continue _ => skipped += 1,
+        Some(_) = ready(None::<i32>) => unreachable!(),
+        else _ => {},
+        complete(report) => report,
+    };
+    assert_eq!(skipped, 2);
+    assert_eq!(report.iter().collect::<Vec<_>>(), vec![BranchEnd::Disabled, BranchEnd::Mismatched]);
+}
+
+#[tokio::test]
+async fn complete_report_compact() {
+    let enabled = false;
+    let report = select! {
+        compact;
+        _ = ready(5), if enabled => unreachable!(),
+        Some(_) = ready(None::<i32>) => unreachable!(),
+        _ = pending::<i32>(), if enabled => unreachable!(),
+        complete(report) => report,
+    };
+    assert_eq!(report.iter().collect::<Vec<_>>(), vec![
+        BranchEnd::Disabled,
+        BranchEnd::Mismatched,
+        BranchEnd::Disabled
+    ]);
+}
+
+#[test]
+fn poll_select_complete_report() {
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut enabled = true;
+    let r = poll_select!(&mut cx,
+        Some(v) = ready(None::<i32>), if enabled => v,
+        else _ => enabled = false,
+        complete(report) => report.get(0).map_or(0, |end| end as i32),
+    );
+    assert_eq!(r, Poll::Ready(BranchEnd::Mismatched as i32));
+}
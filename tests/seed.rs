@@ -0,0 +1,132 @@
+use std::future::{pending, ready};
+
+use async_select::{select, select_loop, select_vec, set_seed, Recorder, Select};
+
+async fn select_seeded(seed: u64) -> usize {
+    select! {
+        seed = seed;
+        v = ready(0) => v,
+        v = ready(1) => v,
+        v = ready(2) => v,
+        v = ready(3) => v,
+    }
+}
+
+async fn select_unseeded() -> usize {
+    select! {
+        v = ready(0) => v,
+        v = ready(1) => v,
+        v = ready(2) => v,
+        v = ready(3) => v,
+    }
+}
+
+#[tokio::test]
+async fn seed_reproducible() {
+    let recorder = Recorder::start();
+    let mut selections = Vec::new();
+    for seed in 0..100 {
+        selections.push(select_seeded(seed).await);
+    }
+    assert_eq!(recorder.finish(), selections);
+
+    let recorder = Recorder::start();
+    for seed in 0..100 {
+        select_seeded(seed).await;
+    }
+    assert_eq!(recorder.finish(), selections);
+    assert!(selections.iter().any(|index| *index != selections[0]));
+}
+
+#[tokio::test]
+async fn seed_loop() {
+    async fn run() -> Vec<usize> {
+        let mut selections = Vec::new();
+        let mut select = |branch| {
+            selections.push(branch);
+            selections.len() == 100
+        };
+        select_loop! {
+            seed = 5;
+            v = repeat ready(0) => if select(v) { break },
+            v = repeat ready(1) => if select(v) { break },
+            v = repeat ready(2) => if select(v) { break },
+        }
+        selections
+    }
+    assert_eq!(run().await, run().await);
+}
+
+#[tokio::test]
+async fn seed_thread() {
+    set_seed(42);
+    let recorder = Recorder::start();
+    for _ in 0..100 {
+        select_unseeded().await;
+    }
+    let selections = recorder.finish();
+
+    set_seed(42);
+    let recorder = Recorder::start();
+    for _ in 0..100 {
+        select_unseeded().await;
+    }
+    assert_eq!(recorder.finish(), selections);
+}
+
+#[tokio::test]
+async fn seed_compact() {
+    let r1 = select! {
+        compact;
+        seed = 9;
+        v = ready(0) => v,
+        v = ready(1) => v,
+        v = ready(2) => v,
+    };
+    let r2 = select! {
+        compact;
+        seed = 9;
+        v = ready(0) => v,
+        v = ready(1) => v,
+        v = ready(2) => v,
+    };
+    assert_eq!(r1, r2);
+}
+
+#[tokio::test]
+async fn seed_library() {
+    let select = || Select::new((ready(0), ready(1), ready(2))).seed(3);
+    assert_eq!(select().await, select().await);
+    let select = || select_vec((0..10).map(ready).collect()).seed(3);
+    assert_eq!(select().await.0, select().await.0);
+}
+
+#[tokio::test]
+async fn record_selections() {
+    let recorder = Recorder::start();
+    select! {
+        biased;
+        _ = pending::<()>() => {},
+        Some(_) = ready(None::<()>) => {},
+        _ = ready(()) => {},
+    };
+    select! {
+        biased;
+        compact;
+        _ = pending::<()>() => {},
+        Some(_) = ready(None::<()>) => {},
+        _ = ready(()) => {},
+    };
+    assert_eq!(recorder.selections(), vec![2, 2]);
+    drop(recorder);
+    let recorder = Recorder::start();
+    assert_eq!(recorder.finish(), Vec::<usize>::new());
+}
+
+#[tokio::test]
+async fn seed_as_pattern() {
+    let r = select! {
+        seed = ready(5) => seed,
+    };
+    assert_eq!(r, 5);
+}
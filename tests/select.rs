@@ -1,9 +1,17 @@
-use std::future::{pending, ready};
+use std::cell::Cell;
+use std::future::{pending, ready, Future, Ready};
+use std::pin::Pin;
+use std::task::{Context, Poll};
 use std::time::Duration;
 
-use async_select::select;
+use async_select::{select, BranchEnd};
+use tokio::sync::mpsc;
 use tokio::time::sleep;
 
+mod common;
+
+use common::YieldTimes;
+
 #[tokio::test]
 async fn expression_type() -> Result<(), std::io::Error> {
     select! {
@@ -50,6 +58,7 @@ async fn biased_no_ready_default() {
 }
 
 #[tokio::test]
+#[allow(deprecated)]
 async fn biased_no_ready() {
     let r = select! {
         biased;
@@ -73,6 +82,7 @@ async fn ready_default() {
 }
 
 #[tokio::test]
+#[allow(deprecated)]
 async fn ready_complete() {
     let r = select! {
         v = ready(5) => v,
@@ -82,6 +92,7 @@ async fn ready_complete() {
 }
 
 #[tokio::test]
+#[allow(deprecated)]
 async fn ready_complete_with_default() {
     let r = select! {
         v = ready(5) => v,
@@ -101,6 +112,7 @@ async fn not_ready_default() {
 }
 
 #[tokio::test]
+#[allow(deprecated)]
 async fn not_ready_complete() {
     let r = select! {
         v = pending() => v,
@@ -111,6 +123,7 @@ async fn not_ready_complete() {
 }
 
 #[tokio::test]
+#[allow(deprecated)]
 async fn not_ready_complete_with_default() {
     let r = select! {
         v = pending() => v,
@@ -133,6 +146,45 @@ async fn all_disabled_panic() {
     };
 }
 
+/// Runs `select` to panic and returns the panic message.
+async fn panic_message(select: impl Future<Output = i32> + Send + 'static) -> String {
+    let panic = tokio::spawn(select).await.unwrap_err().into_panic();
+    match panic.downcast::<String>() {
+        Ok(message) => *message,
+        Err(panic) => panic.downcast::<&str>().unwrap().to_string(),
+    }
+}
+
+#[tokio::test]
+async fn all_disabled_panic_message() {
+    let opt: Option<i32> = none();
+    let line = line!() + 4;
+    let message = panic_message(async move {
+        select! {
+            Some(v) = ready(opt) => v,
+            v = ready(opt.unwrap()), if opt.is_some() => v,
+        }
+    })
+    .await;
+    let expected =
+        format!("branch 1 at tests/select.rs:{line}: `ready(opt.unwrap())`, if `opt.is_some()`: condition is false");
+    assert!(message.contains(&expected), "{message}");
+}
+
+#[tokio::test]
+async fn all_completed_panic_message() {
+    let opt: Option<i32> = none();
+    let line = line!() + 3;
+    let message = panic_message(async move {
+        select! {
+            Some(v) = ready(opt) => v,
+        }
+    })
+    .await;
+    let expected = format!("branch 0 at tests/select.rs:{line}: `ready(opt)`: pattern mismatch");
+    assert!(message.contains(&expected), "{message}");
+}
+
 #[tokio::test]
 async fn all_disabled_default() {
     let opt: Option<i32> = none();
@@ -199,3 +251,226 @@ async fn all_completed_complete_with_default() {
     };
     assert_eq!(r, 7);
 }
+
+struct CountPending<'a> {
+    polls: &'a Cell<usize>,
+}
+
+impl Future for CountPending<'_> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.polls.set(self.polls.get() + 1);
+        Poll::Pending
+    }
+}
+
+#[tokio::test]
+#[deny(deprecated)]
+async fn lint_functions_of_users() {
+    fn pending() -> Ready<i32> {
+        ready(5)
+    }
+    let r = select! {
+        biased;
+        v = pending() => v,
+        default => 6,
+    };
+    assert_eq!(r, 5);
+}
+
+#[tokio::test]
+async fn poll_woken_branches_only() {
+    let polls = Cell::new(0);
+    let r = select! {
+        _ = CountPending { polls: &polls } => unreachable!(),
+        v = YieldTimes { times: 10 } => v,
+    };
+    assert_eq!(r, 5);
+    assert_eq!(polls.get(), 1);
+}
+
+// Without `cached-waker`, branches cloning their wakers are polled on every wakeup.
+#[cfg(feature = "cached-waker")]
+#[tokio::test]
+async fn poll_woken_branches_only_with_channel() {
+    let polls = Cell::new(0);
+    let (_sender, mut receiver) = mpsc::channel::<()>(1);
+    let mut recv = std::pin::pin!(receiver.recv());
+    let idle = std::future::poll_fn(|cx| {
+        polls.set(polls.get() + 1);
+        recv.as_mut().poll(cx)
+    });
+    let r = select! {
+        _ = idle => unreachable!(),
+        v = YieldTimes { times: 10 } => v,
+    };
+    assert_eq!(r, 5);
+    assert_eq!(polls.get(), 1);
+}
+
+#[cfg(feature = "cached-waker")]
+#[tokio::test]
+async fn reuse_cloned_wakers() {
+    let mut polls = 0;
+    let mut reuses = 0;
+    let mut previous: Option<std::task::Waker> = None;
+    let r = select! {
+        v = std::future::poll_fn(|cx| {
+            polls += 1;
+            let waker = cx.waker().clone();
+            if previous.as_ref().is_some_and(|previous| previous.will_wake(&waker)) {
+                reuses += 1;
+            }
+            previous = Some(waker);
+            if polls == 5 {
+                return Poll::Ready(polls);
+            }
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }) => v,
+        _ = pending::<()>() => unreachable!(),
+    };
+    assert_eq!(r, 5);
+    assert_eq!(reuses, 4);
+}
+
+#[derive(Default)]
+struct CountWakes(std::sync::atomic::AtomicUsize);
+
+impl std::task::Wake for CountWakes {
+    fn wake(self: std::sync::Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &std::sync::Arc<Self>) {
+        self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn wake_latest_waker() {
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+
+    let stored = std::cell::RefCell::new(None::<std::task::Waker>);
+    let mut future = std::pin::pin!(async {
+        select! {
+            _ = std::future::poll_fn(|cx| {
+                *stored.borrow_mut() = Some(cx.waker().clone());
+                Poll::<()>::Pending
+            }) => unreachable!(),
+            _ = pending::<()>() => unreachable!(),
+        }
+    });
+    let (first, second) = (Arc::new(CountWakes::default()), Arc::new(CountWakes::default()));
+    let first_waker = std::task::Waker::from(first.clone());
+    assert!(future.as_mut().poll(&mut Context::from_waker(&first_waker)).is_pending());
+    let second_waker = std::task::Waker::from(second.clone());
+    assert!(future.as_mut().poll(&mut Context::from_waker(&second_waker)).is_pending());
+    stored.borrow_mut().take().unwrap().wake();
+    assert_eq!(first.0.load(Ordering::SeqCst), 0);
+    assert_eq!(second.0.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn poll_branches_cloned_waker() {
+    let r = select! {
+        _ = sleep(Duration::from_millis(5)) => 5,
+        _ = pending::<()>() => unreachable!(),
+    };
+    assert_eq!(r, 5);
+}
+
+fn channel(values: impl IntoIterator<Item = i32>) -> mpsc::UnboundedReceiver<i32> {
+    let (tx, rx) = mpsc::unbounded_channel();
+    values.into_iter().for_each(|v| tx.send(v).unwrap());
+    rx
+}
+
+#[tokio::test]
+async fn repeat_until_completed() {
+    let mut rx = channel([1, 2, 3]);
+    let mut sum = 0;
+    let r = select! {
+        Some(v) = repeat rx.recv() => sum += v,
+        complete => sum,
+    };
+    assert_eq!(r, 6);
+}
+
+#[tokio::test]
+async fn repeat_until_others() {
+    let mut rx = channel([1, 2, 3]);
+    let mut values = vec![];
+    let r = select! {
+        biased;
+        Some(v) = repeat rx.recv() => values.push(v),
+        v = YieldTimes { times: 2 } => v,
+    };
+    assert_eq!(r, 5);
+    assert_eq!(values, vec![1, 2, 3]);
+}
+
+#[tokio::test]
+async fn repeat_with_others_borrowing_mutably() {
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let mut n = 0;
+    let r = select! {
+        biased;
+        v = rx.recv() => v,
+        _ = repeat ready(()), if n < 3 => {
+            n += 1;
+            if n == 3 {
+                tx.send(5).unwrap();
+            }
+        },
+    };
+    assert_eq!(r, Some(5));
+    assert_eq!(n, 3);
+}
+
+#[tokio::test]
+async fn repeat_conditionally() {
+    let mut i = 0;
+    let r = select! {
+        v = repeat ready(i), if i < 3 => i += v + 1,
+        complete => i,
+    };
+    assert_eq!(r, 3);
+}
+
+#[tokio::test]
+async fn repeat_mismatched_disabled() {
+    let evaluations = Cell::new(0);
+    let mut i = 0;
+    let report = select! {
+        Some(v) = repeat {
+            evaluations.set(evaluations.get() + 1);
+            ready(None::<i32>)
+        } => unreachable!("{v}"),
+        _ = repeat ready(()), if i < 3 => i += 1,
+        complete(report) => report,
+    };
+    assert_eq!(i, 3);
+    assert_eq!(evaluations.get(), 1);
+    assert_eq!(report.get(0), Some(BranchEnd::Mismatched));
+}
+
+#[tokio::test]
+async fn repeat_labeled_continue() {
+    let mut completes = vec![];
+    'outer: for x in 0..3 {
+        let mut i = 0;
+        select! {
+            v = repeat ready(i), if i < 3 => {
+                i += 1;
+                if v == 1 && x == 1 {
+                    continue 'outer;
+                }
+            },
+            complete => completes.push(x),
+        }
+    }
+    assert_eq!(completes, vec![0, 2]);
+}
@@ -0,0 +1,99 @@
+use std::future::{pending, ready, Future};
+use std::pin::Pin;
+
+use async_select::{select_array, SelectArray};
+#[cfg(feature = "alloc")]
+use async_select::{select_iter, select_vec};
+
+type BoxFuture = Pin<Box<dyn Future<Output = usize>>>;
+
+#[tokio::test]
+async fn array_ready() {
+    let futures: [BoxFuture; 3] = [Box::pin(pending()), Box::pin(ready(5)), Box::pin(pending())];
+    let (index, output, remaining) = select_array(futures).await;
+    assert_eq!(index, 1);
+    assert_eq!(output, 5);
+    assert!(remaining[0].is_some());
+    assert!(remaining[1].is_none());
+    assert!(remaining[2].is_some());
+}
+
+#[tokio::test]
+async fn array_biased() {
+    let (index, output, _) = select_array([ready(0), ready(1), ready(2)]).biased().await;
+    assert_eq!(index, 0);
+    assert_eq!(output, 0);
+}
+
+#[tokio::test]
+async fn array_woken() {
+    let (sender, receiver) = tokio::sync::oneshot::channel::<usize>();
+    let (_sender, pending) = tokio::sync::oneshot::channel::<usize>();
+    let handle = tokio::spawn(async move { select_array([pending, receiver]).await.1.unwrap() });
+    tokio::task::yield_now().await;
+    sender.send(5).unwrap();
+    assert_eq!(handle.await.unwrap(), 5);
+}
+
+#[tokio::test]
+#[should_panic(expected = "select_array: no futures")]
+async fn array_empty() {
+    select_array::<std::future::Ready<()>, 0>([]).await;
+}
+
+#[tokio::test]
+async fn array_remaining() {
+    let mut outputs = vec![];
+    let (_, output, mut remaining) = select_array([ready(0), ready(1), ready(2)]).await;
+    outputs.push(output);
+    while remaining.iter().any(Option::is_some) {
+        let (index, output, rest) = SelectArray::conditional(remaining).await;
+        assert!(rest[index].is_none());
+        outputs.push(output);
+        remaining = rest;
+    }
+    outputs.sort();
+    assert_eq!(outputs, vec![0, 1, 2]);
+}
+
+#[tokio::test]
+#[should_panic(expected = "select_array: no futures")]
+async fn array_all_none() {
+    SelectArray::<std::future::Ready<()>, 2>::conditional([None, None]).await;
+}
+
+#[cfg(feature = "alloc")]
+#[tokio::test]
+async fn vec_ready() {
+    let futures: Vec<BoxFuture> = vec![Box::pin(pending()), Box::pin(pending()), Box::pin(ready(5))];
+    let (index, output, remaining) = select_vec(futures).await;
+    assert_eq!(index, 2);
+    assert_eq!(output, 5);
+    assert_eq!(remaining.len(), 2);
+}
+
+#[cfg(feature = "alloc")]
+#[tokio::test]
+async fn vec_remaining() {
+    let futures: Vec<_> = (0..5).map(ready).collect();
+    let (index, output, remaining) = select_vec(futures).biased().await;
+    assert_eq!((index, output), (0, 0));
+    let (index, output, remaining) = select_vec(remaining).biased().await;
+    assert_eq!((index, output), (0, 1));
+    assert_eq!(remaining.len(), 3);
+}
+
+#[cfg(feature = "alloc")]
+#[tokio::test]
+async fn iter_ready() {
+    let (index, output, remaining) = select_iter((0..3).map(|i| Box::pin(async move { i * 2 }))).biased().await;
+    assert_eq!((index, output), (0, 0));
+    assert_eq!(remaining.len(), 2);
+}
+
+#[cfg(feature = "alloc")]
+#[tokio::test]
+#[should_panic(expected = "select_vec: no futures")]
+async fn vec_empty() {
+    select_vec(Vec::<std::future::Ready<()>>::new()).await;
+}
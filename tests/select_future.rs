@@ -0,0 +1,100 @@
+use std::future::{pending, ready, Future};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use async_select::{Select, Selected};
+
+#[tokio::test]
+async fn select_ready() {
+    let r = Select::new((pending::<()>(), ready(5))).await;
+    assert_eq!(r, Selected::_1(5));
+}
+
+#[tokio::test]
+async fn select_biased() {
+    let r = Select::new((pending::<()>(), ready(5), ready("a"))).biased().await;
+    assert_eq!(r, Selected::_1(5));
+}
+
+#[tokio::test]
+async fn select_nonblocking() {
+    let r = Select::new((pending::<()>(), pending::<i32>())).nonblocking().await;
+    assert_eq!(r, Selected::Default);
+}
+
+#[tokio::test]
+async fn select_complete() {
+    let r = Select::conditional((None::<std::future::Ready<()>>, None::<std::future::Pending<()>>)).nonblocking().await;
+    assert_eq!(r, Selected::Complete);
+}
+
+#[tokio::test]
+async fn select_conditional() {
+    let r = Select::conditional((None::<std::future::Ready<()>>, Some(ready(6)), Some(pending::<()>()))).biased().await;
+    assert_eq!(r, Selected::_1(6));
+}
+
+#[tokio::test]
+async fn select_remaining() {
+    let mut select = Select::new((ready(1), pending::<()>(), ready("a"))).biased();
+    assert_eq!((&mut select).await, Selected::_0(1));
+    assert_eq!((&mut select).await, Selected::_2("a"));
+    let mut select = select.nonblocking();
+    assert_eq!((&mut select).await, Selected::Default);
+    let (first, second, third) = select.into_inner();
+    assert!(first.is_none());
+    assert!(second.is_some());
+    assert!(third.is_none());
+}
+
+#[tokio::test]
+async fn select_woken() {
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    let handle = tokio::spawn(async move { Select::new((pending::<()>(), receiver)).await });
+    tokio::task::yield_now().await;
+    sender.send(5).unwrap();
+    assert_eq!(handle.await.unwrap(), Selected::_1(Ok(5)));
+}
+
+#[tokio::test]
+async fn select_in_struct() {
+    struct Sum<T: async_select::Branches> {
+        select: Select<T>,
+        sum: i32,
+    }
+
+    impl<T: async_select::Branches<Output = Selected<i32, i32, i32>> + Unpin> Future for Sum<T> {
+        type Output = i32;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            loop {
+                match Pin::new(&mut self.select).poll(cx) {
+                    Poll::Pending => return Poll::Pending,
+                    Poll::Ready(Selected::Complete) => return Poll::Ready(self.sum),
+                    Poll::Ready(Selected::_0(v) | Selected::_1(v) | Selected::_2(v)) => self.sum += v,
+                    Poll::Ready(_) => unreachable!(),
+                }
+            }
+        }
+    }
+
+    let sum = Sum { select: Select::new((ready(1), ready(2), ready(3))), sum: 0 };
+    assert_eq!(sum.await, 6);
+}
+
+#[tokio::test]
+async fn select_pinned() {
+    let mut select = Box::pin(Select::new((async { 5 }, pending::<()>())));
+    assert_eq!(select.as_mut().await, Selected::_0(5));
+    let poll = std::future::poll_fn(|cx| Poll::Ready(select.as_mut().poll(cx))).await;
+    assert_eq!(poll, Poll::Pending);
+}
+
+#[test]
+fn size_by_arity() {
+    type Branch = Option<std::future::Ready<()>>;
+    let one = std::mem::size_of::<Select<(Branch,)>>();
+    let two = std::mem::size_of::<Select<(Branch, Branch)>>();
+    assert!(one < 64, "{one}");
+    assert!(two < 64, "{two}");
+}
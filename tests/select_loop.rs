@@ -0,0 +1,127 @@
+use std::future::{pending, ready};
+
+use async_select::select_loop;
+
+mod common;
+
+use common::YieldTimes;
+
+#[tokio::test]
+async fn futures_kept_across_iterations() {
+    let mut n = 0;
+    let r = select_loop! {
+        biased;
+        v = YieldTimes { times: 3 } => break v,
+        _ = repeat ready(()) => {
+            n += 1;
+            if n > 100 {
+                break 0;
+            }
+        },
+    };
+    assert_eq!(r, 5);
+    assert_eq!(n, 3);
+}
+
+#[tokio::test]
+async fn futures_borrowing_mutably() {
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let mut n = 0;
+    let r = select_loop! {
+        biased;
+        v = rx.recv() => break v,
+        _ = repeat ready(()), if n < 3 => {
+            n += 1;
+            if n == 3 {
+                tx.send(5).unwrap();
+            }
+        },
+    };
+    assert_eq!(r, Some(5));
+    assert_eq!(n, 3);
+}
+
+#[tokio::test]
+async fn repeat_rearm() {
+    let mut values = vec![];
+    let mut i = 0;
+    select_loop! {
+        v = repeat ready(i), if i < 5 => {
+            values.push(v);
+            i += 1;
+        },
+        complete => break,
+    };
+    assert_eq!(values, vec![0, 1, 2, 3, 4]);
+}
+
+#[tokio::test]
+async fn ready_once() {
+    let mut values = vec![];
+    select_loop! {
+        v = ready(1) => values.push(v),
+        v = ready(2) => values.push(v),
+        v = ready(3) => values.push(v),
+        complete => break,
+    };
+    values.sort();
+    assert_eq!(values, vec![1, 2, 3]);
+}
+
+#[tokio::test]
+async fn biased_ready_once() {
+    let mut values = vec![];
+    select_loop! {
+        biased;
+        v = ready(1) => values.push(v),
+        v = ready(2) => values.push(v),
+        v = ready(3) => values.push(v),
+        complete => break,
+    };
+    assert_eq!(values, vec![1, 2, 3]);
+}
+
+#[tokio::test]
+async fn continue_loop() {
+    let mut i = 0;
+    let r = select_loop! {
+        v = repeat ready(i) => {
+            i += 1;
+            if v < 3 {
+                continue;
+            }
+            break v;
+        },
+    };
+    assert_eq!(r, 3);
+}
+
+#[tokio::test]
+async fn refutable_disabled() {
+    let mut values = vec![];
+    select_loop! {
+        Some(v) = ready(None::<i32>) => values.push(v),
+        v = ready(2) => values.push(v),
+        complete => break,
+    };
+    assert_eq!(values, vec![2]);
+}
+
+#[tokio::test]
+async fn default_after_ready() {
+    let mut values = vec![];
+    select_loop! {
+        v = ready(1) => values.push(v),
+        _ = pending::<()>() => unreachable!(),
+        default => break,
+    };
+    assert_eq!(values, vec![1]);
+}
+
+#[tokio::test]
+#[should_panic(expected = "all branches are disabled or completed")]
+async fn all_completed_panic() {
+    select_loop! {
+        _ = ready(1) => {},
+    }
+}
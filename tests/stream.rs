@@ -0,0 +1,119 @@
+use std::future::{pending, ready};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use async_select::{select, select_loop, Stream};
+use tokio::sync::mpsc;
+
+mod common;
+
+use common::Iter;
+
+#[tokio::test]
+async fn stream_item() {
+    let mut stream = Iter::new([Poll::Pending, Poll::Ready(5), Poll::Ready(6)]);
+    let r = select! {
+        v = stream in &mut stream => v,
+        _ = pending::<()>() => unreachable!(),
+    };
+    assert_eq!(r, 5);
+    let r = select! {
+        v = stream in &mut stream => v,
+        _ = pending::<()>() => unreachable!(),
+    };
+    assert_eq!(r, 6);
+}
+
+#[tokio::test]
+async fn stream_end() {
+    let r = select! {
+        _ = stream in Iter::new([]) => unreachable!(),
+        complete => 5,
+    };
+    assert_eq!(r, 5);
+}
+
+#[tokio::test]
+async fn stream_pattern_mismatch() {
+    let r = select! {
+        5 = stream in Iter::new([Poll::Ready(4), Poll::Ready(5)]) => unreachable!(),
+        complete => 5,
+    };
+    assert_eq!(r, 5);
+}
+
+fn none<T>() -> Option<T> {
+    None
+}
+
+#[tokio::test]
+async fn stream_conditionally() {
+    let opt: Option<Iter> = none();
+    let r = select! {
+        v = stream in opt.unwrap(), if opt.is_some() => v,
+        complete => 5,
+    };
+    assert_eq!(r, 5);
+}
+
+#[tokio::test]
+async fn stream_repeat() {
+    let mut items = vec![];
+    let r = select! {
+        v = repeat stream in Iter::new([Poll::Ready(1), Poll::Pending, Poll::Ready(2)]) => items.push(v),
+        complete => 3,
+    };
+    assert_eq!(r, 3);
+    assert_eq!(items, vec![1, 2]);
+}
+
+#[tokio::test]
+async fn stream_loop() {
+    let mut items = vec![];
+    select_loop! {
+        v = stream in Iter::new([Poll::Ready(1), Poll::Pending, Poll::Ready(2)]) => items.push(v),
+        complete => break,
+    }
+    assert_eq!(items, vec![1, 2]);
+}
+
+#[tokio::test]
+async fn stream_pinned() {
+    let mut stream = Box::pin(Iter::new([Poll::Ready(5)]));
+    let r = select! {
+        v = stream in stream.as_mut() => v,
+    };
+    assert_eq!(r, 5);
+}
+
+#[tokio::test]
+async fn stream_variable() {
+    let stream = ready(5);
+    let r = select! {
+        v = stream => v,
+    };
+    assert_eq!(r, 5);
+}
+
+struct Rx<'a>(&'a mut mpsc::UnboundedReceiver<i32>);
+
+impl Stream for Rx<'_> {
+    type Item = i32;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.0.poll_recv(cx)
+    }
+}
+
+#[tokio::test]
+async fn stream_borrowing_mutably() {
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    [1, 2, 3].into_iter().for_each(|v| tx.send(v).unwrap());
+    drop(tx);
+    let mut sum = 0;
+    let r = select! {
+        v = repeat stream in Rx(&mut rx) => sum += v,
+        complete => sum,
+    };
+    assert_eq!(r, 6);
+}
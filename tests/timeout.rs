@@ -0,0 +1,115 @@
+use std::future::{pending, ready};
+use std::time::{Duration, Instant};
+
+use async_select::{select, select_loop, AsyncIoTimer, Timer};
+
+#[tokio::test]
+async fn timeout_elapsed() {
+    let start = Instant::now();
+    let r = select! {
+        _ = pending::<()>() => unreachable!(),
+        timeout(Duration::from_millis(20)) => 5,
+    };
+    assert_eq!(r, 5);
+    assert!(start.elapsed() >= Duration::from_millis(20));
+}
+
+#[tokio::test]
+async fn timeout_not_elapsed() {
+    let r = select! {
+        v = ready(6) => v,
+        timeout(Duration::from_secs(60)) => 5,
+    };
+    assert_eq!(r, 6);
+}
+
+#[tokio::test]
+async fn timeout_only() {
+    let r = select! {
+        timeout(Duration::from_millis(1)) => 5,
+    };
+    assert_eq!(r, 5);
+}
+
+#[tokio::test]
+async fn timeout_disabled_branches() {
+    let r = select! {
+        Some(v) = ready(None::<i32>) => v,
+        timeout(Duration::from_millis(1)) => 5,
+        complete => 6,
+    };
+    assert_eq!(r, 5);
+}
+
+#[tokio::test]
+async fn timeout_async_io() {
+    let r = select! {
+        timer = AsyncIoTimer;
+        _ = pending::<()>() => unreachable!(),
+        timeout(Duration::from_millis(1)) => 5,
+    };
+    assert_eq!(r, 5);
+}
+
+// Feature `tokio` is enabled too, but there is no tokio runtime.
+#[test]
+fn timeout_default_outside_tokio() {
+    let r = async_io::block_on(async {
+        select! {
+            _ = pending::<()>() => unreachable!(),
+            timeout(Duration::from_millis(1)) => 5,
+        }
+    });
+    assert_eq!(r, 5);
+}
+
+#[tokio::test]
+async fn timeout_custom_timer() {
+    struct Immediate;
+
+    impl Timer for Immediate {
+        type Sleep = std::future::Ready<()>;
+
+        fn sleep(_duration: Duration) -> Self::Sleep {
+            ready(())
+        }
+    }
+
+    let r = select! {
+        timer = Immediate;
+        _ = pending::<()>() => unreachable!(),
+        timeout(Duration::from_secs(60)) => 5,
+    };
+    assert_eq!(r, 5);
+}
+
+#[tokio::test]
+async fn timeout_loop() {
+    let mut count = 0;
+    let r = select_loop! {
+        _ = repeat tokio::time::sleep(Duration::from_millis(1)) => count += 1,
+        timeout(Duration::from_millis(50)) => break count,
+    };
+    assert!(r > 1);
+}
+
+#[tokio::test]
+async fn timeout_compact() {
+    let r = select! {
+        compact;
+        _ = pending::<()>() => unreachable!(),
+        timeout(Duration::from_millis(1)) => 5,
+    };
+    assert_eq!(r, 5);
+}
+
+#[tokio::test]
+async fn timeout_as_pattern() {
+    #[allow(non_camel_case_types)]
+    struct timeout(i32);
+
+    let r = select! {
+        timeout(v) = ready(timeout(5)) => v,
+    };
+    assert_eq!(r, 5);
+}
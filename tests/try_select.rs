@@ -0,0 +1,111 @@
+use std::future::{pending, ready};
+
+use async_select::{select, try_select};
+
+#[tokio::test]
+async fn try_select_ok() {
+    let r = try_select! {
+        v = ready(Ok::<i32, &str>(5)) => v + 1,
+        v = pending::<Result<i32, &str>>() => v,
+    };
+    assert_eq!(r, Ok(6));
+}
+
+#[tokio::test]
+async fn try_select_err() {
+    let r = try_select! {
+        v = ready(Err::<i32, &str>("failure")) => v + 1,
+        v = pending::<Result<i32, &str>>() => v,
+    };
+    assert_eq!(r, Err("failure"));
+}
+
+#[tokio::test]
+async fn try_select_question_mark() {
+    async fn run(fail: bool) -> Result<i32, &'static str> {
+        let v = try_select! {
+            v = ready(if fail { Err("failure") } else { Ok(5) }) => v,
+        }?;
+        Ok(v + 1)
+    }
+    assert_eq!(run(false).await, Ok(6));
+    assert_eq!(run(true).await, Err("failure"));
+}
+
+#[tokio::test]
+async fn try_select_error_clause() {
+    let r = try_select! {
+        v = ready(Err::<i32, i32>(5)) => v,
+        error(e) => e * 2,
+    };
+    assert_eq!(r, 10);
+
+    let r = try_select! {
+        v = ready(Ok::<i32, i32>(5)) => v,
+        error(e) => e * 2,
+    };
+    assert_eq!(r, 5);
+}
+
+#[tokio::test]
+async fn try_select_refutable() {
+    let r = try_select! {
+        biased;
+        Some(v) = ready(Ok::<Option<i32>, ()>(None)) => v,
+        v = ready(Ok(6)) => v,
+    };
+    assert_eq!(r, Ok(6));
+
+    let r = try_select! {
+        biased;
+        Some(v) = ready(Err::<Option<i32>, &str>("failure")) => v,
+        v = ready(Ok(6)) => v,
+    };
+    assert_eq!(r, Err("failure"));
+}
+
+#[tokio::test]
+async fn try_select_default_complete() {
+    let r = try_select! {
+        _ = pending::<Result<i32, ()>>() => unreachable!(),
+        default => 5,
+    };
+    assert_eq!(r, Ok(5));
+
+    let r = try_select! {
+        Some(v) = ready(Ok::<Option<i32>, ()>(None)) => v,
+        complete => 6,
+    };
+    assert_eq!(r, Ok(6));
+}
+
+#[tokio::test]
+async fn try_select_repeat() {
+    let mut i = 0;
+    let r = try_select! {
+        v = repeat ready(if i < 3 { Ok::<i32, i32>(i) } else { Err(i) }) => i = v + 1,
+        _ = pending::<Result<(), i32>>() => unreachable!(),
+    };
+    assert_eq!(r, Err(3));
+}
+
+#[tokio::test]
+async fn try_select_compact() {
+    let r = try_select! {
+        compact;
+        v = ready(Err::<i32, i32>(5)) => v,
+        error(e) => e * 2,
+    };
+    assert_eq!(r, 10);
+}
+
+#[tokio::test]
+async fn select_error_as_pattern() {
+    #[allow(non_camel_case_types)]
+    struct error(i32);
+
+    let r = select! {
+        error(v) = ready(error(5)) => v,
+    };
+    assert_eq!(r, 5);
+}
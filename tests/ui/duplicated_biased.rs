@@ -0,0 +1,11 @@
+use async_select::select;
+
+async fn select() {
+    select! {
+        biased;
+        biased;
+        _ = core::future::ready(()) => {},
+    }
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use async_select::select;
+
+async fn select() {
+    select! {
+        _ = core::future::ready(()) => {},
+        complete => {},
+        complete => {},
+    }
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use async_select::try_select;
+
+async fn select() {
+    let _ = try_select! {
+        _ = core::future::ready(Ok::<(), ()>(())) => {},
+        error(_) => {},
+        error(_) => {},
+    };
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use async_select::select;
+
+async fn select() {
+    select! {
+        fair = random;
+        fair = round_robin;
+        _ = core::future::ready(()) => {},
+    }
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use async_select::select;
+
+async fn select() {
+    select! {
+        keep _a;
+        keep _b;
+        _ = core::future::ready(()) => {},
+    }
+}
+
+fn main() {}
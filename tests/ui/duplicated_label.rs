@@ -0,0 +1,10 @@
+use async_select::select;
+
+async fn select() {
+    select! {
+        'ready: _ = core::future::ready(()) => {},
+        'ready: _ = core::future::ready(()) => {},
+    }
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use async_select::select;
+
+async fn select() {
+    select! {
+        nopanic;
+        nopanic;
+        _ = core::future::ready(()) => {},
+    }
+}
+
+fn main() {}
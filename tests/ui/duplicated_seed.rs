@@ -0,0 +1,11 @@
+use async_select::select;
+
+async fn select() {
+    select! {
+        seed = 1;
+        seed = 2;
+        _ = core::future::ready(()) => {},
+    }
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use async_select::select;
+
+async fn select() {
+    select! {
+        _ = core::future::ready(()) => {},
+        timeout(core::time::Duration::from_secs(1)) => {},
+        timeout(core::time::Duration::from_secs(2)) => {},
+    }
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use async_select::select;
+
+async fn select() {
+    select! {
+        timer = async_select::TokioTimer;
+        timer = async_select::TokioTimer;
+        _ = core::future::ready(()) => {},
+        timeout(core::time::Duration::from_secs(1)) => {},
+    }
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use async_select::select;
+
+async fn select() {
+    for x in 0..2 {
+        select! {
+            Some(v) = core::future::ready(None::<i32>) => v,
+            else continue _ => {
+                if x == 0 {
+                    continue;
+                }
+                break;
+            },
+            _ = core::future::pending::<()>() => unreachable!(),
+        };
+    }
+}
+
+fn main() {}
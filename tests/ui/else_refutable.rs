@@ -0,0 +1,11 @@
+use async_select::select;
+
+async fn select() -> i32 {
+    select! {
+        Some(1) = core::future::ready(Some(2)) => 1,
+        else Some(3) => {},
+        complete => 9,
+    }
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use async_select::select;
+
+async fn select() {
+    select! {
+        Some(_) = core::future::ready(None::<()>) => {},
+        else
+    }
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use async_select::join;
+
+async fn join() {
+    join! {
+        'five: v = core::future::ready(5) => v,
+    };
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use async_select::join;
+
+async fn join() {
+    join! {
+        nopanic;
+        v = core::future::ready(5) => v,
+    };
+}
+
+fn main() {}
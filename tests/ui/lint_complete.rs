@@ -0,0 +1,13 @@
+#![deny(deprecated)]
+
+use async_select::select;
+
+async fn select() -> i32 {
+    select! {
+        v = core::future::ready(5) => v,
+        (a, _) = core::future::ready((6, 7)) => a,
+        complete => 6,
+    }
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+#![deny(deprecated)]
+
+use async_select::select;
+
+async fn select() -> i32 {
+    select! {
+        v = core::future::ready(6), if false => v,
+        v = core::future::ready(5) => v,
+    }
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+#![deny(deprecated)]
+
+use async_select::select;
+
+async fn select() -> i32 {
+    select! {
+        1 | 2 = core::future::ready(1) => 1,
+        else 2 | _ => {},
+        complete => 0,
+    }
+}
+
+fn main() {}
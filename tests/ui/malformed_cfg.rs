@@ -0,0 +1,11 @@
+use async_select::select;
+
+async fn select() {
+    select! {
+        #[cfg]
+        _ = core::future::ready(()) => {},
+        _ = core::future::pending::<()>() => unreachable!(),
+    }
+}
+
+fn main() {}
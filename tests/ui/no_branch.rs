@@ -0,0 +1,7 @@
+use async_select::select;
+
+async fn select() {
+    select! {}
+}
+
+fn main() {}
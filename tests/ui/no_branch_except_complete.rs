@@ -0,0 +1,9 @@
+use async_select::select;
+
+async fn select() {
+    select! {
+        complete => {},
+    }
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use async_select::select;
+
+async fn select() {
+    select! {
+        complete => {},
+        default => {},
+    }
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use async_select::poll_select;
+use core::task::{Context, Poll};
+
+fn poll(cx: &mut Context<'_>) -> Poll<i32> {
+    poll_select!(cx,
+        Some(v) = core::future::ready(Some(5)) => v,
+        else continue,
+        complete => 6,
+    )
+}
+
+fn main() {}
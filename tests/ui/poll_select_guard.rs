@@ -0,0 +1,11 @@
+use async_select::poll_select;
+use core::task::{Context, Poll};
+
+fn poll(cx: &mut Context<'_>) -> Poll<i32> {
+    poll_select!(cx,
+        v = core::future::ready(5) if v > 5 => v,
+        complete => 6,
+    )
+}
+
+fn main() {}
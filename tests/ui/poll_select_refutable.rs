@@ -0,0 +1,10 @@
+use async_select::poll_select;
+use core::task::{Context, Poll};
+
+fn poll(cx: &mut Context<'_>, receiver: &mut tokio::sync::oneshot::Receiver<i32>) -> Poll<i32> {
+    poll_select!(cx,
+        Ok(v) = receiver => v,
+    )
+}
+
+fn main() {}
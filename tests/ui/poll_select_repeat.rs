@@ -0,0 +1,11 @@
+use async_select::poll_select;
+use core::task::{Context, Poll};
+
+fn poll(cx: &mut Context<'_>) -> Poll<i32> {
+    poll_select!(cx,
+        v = repeat core::future::ready(5) => {},
+        v = core::future::ready(6) => v,
+    )
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use async_select::poll_select;
+use core::task::{Context, Poll};
+
+fn poll(cx: &mut Context<'_>) -> Poll<i32> {
+    poll_select!(cx,
+        seed = 7;
+        v = core::future::ready(5) => v,
+        v = core::future::ready(6) => v,
+    )
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+use async_select::select;
+
+async fn select() {
+    for x in 0..3 {
+        let mut i = 0;
+        select! {
+            v = repeat core::future::ready(i), if i < 3 => {
+                i += 1;
+                if v == x {
+                    continue;
+                }
+                if v > x {
+                    break;
+                }
+                for _ in 0..v {
+                    continue;
+                }
+            },
+            complete => {},
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use async_select::select_loop;
+
+async fn select_loop() {
+    select_loop! {
+        nopanic;
+        _ = core::future::ready(()) => break,
+    }
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use async_select::select;
+
+async fn select() {
+    select! {
+        timer = async_select::TokioTimer;
+        _ = core::future::ready(()) => {},
+    }
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use async_select::select;
+
+async fn select() {
+    select! {
+        _ = core::future::ready(()), weight 0 => {},
+        _ = core::future::ready(()) => {},
+    }
+}
+
+fn main() {}
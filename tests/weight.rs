@@ -0,0 +1,91 @@
+use std::future::{pending, ready};
+
+#[cfg(feature = "recording")]
+use async_select::Recorder;
+use async_select::{select, select_loop};
+
+const ROUNDS: usize = 5000;
+
+#[tokio::test]
+async fn weight_proportional() {
+    let mut counts = [0; 2];
+    for _ in 0..ROUNDS {
+        let branch = select! {
+            v = ready(0), weight 4 => v,
+            v = ready(1) => v,
+        };
+        counts[branch] += 1;
+    }
+    let ratio = counts[0] as f64 / counts[1] as f64;
+    assert!((3.0..5.5).contains(&ratio), "counts: {counts:?}");
+}
+
+#[tokio::test]
+async fn weight_among_ready() {
+    let mut counts = [0; 3];
+    for _ in 0..ROUNDS {
+        let branch = select! {
+            v = pending::<usize>(), weight 100 => v,
+            v = ready(1), weight 3 => v,
+            v = ready(2), weight 1 => v,
+        };
+        counts[branch] += 1;
+    }
+    assert_eq!(counts[0], 0);
+    let ratio = counts[1] as f64 / counts[2] as f64;
+    assert!((2.0..4.5).contains(&ratio), "counts: {counts:?}");
+}
+
+#[tokio::test]
+async fn weight_no_starvation() {
+    let mut control = 0;
+    let mut bulk = 0;
+    select_loop! {
+        _ = repeat ready(()), weight 8 => control += 1,
+        _ = repeat ready(()) => {
+            bulk += 1;
+            if control + bulk >= ROUNDS {
+                break;
+            }
+        },
+    };
+    assert!(bulk > ROUNDS / 20, "control: {control}, bulk: {bulk}");
+    assert!(control > bulk, "control: {control}, bulk: {bulk}");
+}
+
+#[tokio::test]
+async fn weight_condition() {
+    let enabled = false;
+    let r = select! {
+        v = ready(0), if enabled, weight 10 => v,
+        v = ready(1), weight 1 => v,
+    };
+    assert_eq!(r, 1);
+}
+
+#[cfg(feature = "recording")]
+#[tokio::test]
+async fn weight_seeded() {
+    async fn run() -> Vec<usize> {
+        let recorder = Recorder::start();
+        for seed in 0..100 {
+            select! {
+                seed = seed;
+                _ = ready(()), weight 2 => {},
+                _ = ready(()), weight 3 => {},
+                _ = ready(()) => {},
+            };
+        }
+        recorder.finish()
+    }
+    assert_eq!(run().await, run().await);
+}
+
+#[tokio::test]
+async fn weight_as_future() {
+    let weight = ready(5);
+    let r = select! {
+        v = weight => v,
+    };
+    assert_eq!(r, 5);
+}